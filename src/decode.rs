@@ -1,34 +1,34 @@
 use ruzstd::frame::ReadFrameHeaderError;
 use ruzstd::frame_decoder::FrameDecoderError;
 use ruzstd::{BlockDecodingStrategy, FrameDecoder};
-use std::io::Read;
-use std::io::Seek;
-
-use crate::log;
+use std::io::{BufRead, BufReader, Cursor, Read};
 
 /* Decompress the buffer if it is compressed
 */
 pub fn decodebuffer(input: &[u8]) -> Result<Vec<u8>, String> {
-    match &input[0..6] {
+    if input.len() < 6 {
+        return Err("input is too short".into());
+    }
+    match input[0..6] {
         //xz (.xz) format description, starts with 0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00
-        &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00] => {
+        [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00] => {
             console_log!("xz compression detected");
-            Err("xz compression not supported".into())
+            decodebuffer_xz(input)
         }
         // zstd (.zst) format description, starts with 0x28, 0xb5, 0x2f, 0xfd
-        &[0x28, 0xb5, 0x2f, 0xfd, _, _] => {
+        [0x28, 0xb5, 0x2f, 0xfd, _, _] => {
             console_log!("zstd compression detected");
-            Ok(decodebuffer_zstd(input))
+            decodebuffer_zstd(input)
         }
         //Gzip (.gz) format description, starts with 0x1f, 0x8b, 0x08
-        &[0x1f, 0x8b, 0x08, _, _, _] => {
+        [0x1f, 0x8b, 0x08, _, _, _] => {
             console_log!("gz compression detected");
-            Err("gz compression not supported".into())
+            decodebuffer_gz(input)
         }
         //Zip (.zip) format description, starts with 0x50, 0x4b, 0x03, 0x04 (unless empty — then the last two are 0x05, 0x06 or 0x06, 0x06)
-        &[0x50, 0x4b, 0x03, 0x04, _, _] => {
+        [0x50, 0x4b, 0x03, 0x04, _, _] => {
             console_log!("zip compression detected");
-            Err("zip compression not supported".into())
+            decodebuffer_zip(input, None)
         }
         _ => {
             console_log!("No compression detected");
@@ -37,57 +37,274 @@ pub fn decodebuffer(input: &[u8]) -> Result<Vec<u8>, String> {
     }
 }
 
+pub fn decodebuffer_gz(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut result = Vec::with_capacity(input.len());
+    // graph-tool files may consist of multiple gzip members
+    flate2::read::MultiGzDecoder::new(input)
+        .read_to_end(&mut result)
+        .map_err(|e| format!("gz decoding failed: {}", e))?;
+    Ok(result)
+}
+
+pub fn decodebuffer_xz(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut result = Vec::with_capacity(input.len());
+    lzma_rs::xz_decompress(&mut std::io::BufReader::new(input), &mut result)
+        .map_err(|e| format!("xz decoding failed: {}", e))?;
+    Ok(result)
+}
+
+/* Extract a graph from a zip archive
+ *
+ * If no entry name is given, the first entry ending with `.gt` is used.
+ * The extracted entry is decoded again, such that compressed
+ * graphs (e.g. `.gt.zst`) inside the archive work as well.
+ */
+pub fn decodebuffer_zip(input: &[u8], name: Option<&str>) -> Result<Vec<u8>, String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(input))
+        .map_err(|e| format!("zip decoding failed: {}", e))?;
+
+    let matches = |entry: &str| match name {
+        Some(name) => entry == name,
+        None => entry.ends_with(".gt") || entry.contains(".gt."),
+    };
+    let index = (0..archive.len())
+        .find(|&i| match archive.by_index_raw(i) {
+            Ok(file) => matches(file.name()),
+            Err(_) => false,
+        })
+        .ok_or(match name {
+            Some(name) => format!("zip archive does not contain '{}'", name),
+            None => "zip archive does not contain a .gt file".to_string(),
+        })?;
+
+    let mut file = archive
+        .by_index(index)
+        .map_err(|e| format!("zip entry {}: {}", index, e))?;
+    let name = file.name().to_string();
+    console_log!("extracting '{}' from zip archive", name);
+
+    let mut result = Vec::new();
+    file.read_to_end(&mut result)
+        .map_err(|e| format!("zip entry '{}': {}", name, e))?;
+
+    decodebuffer(&result)
+}
+
+/* Decompress the input while it is read
+ *
+ * The compression is detected like in `decodebuffer`. zstd and gzip
+ * input is decompressed on the fly, such that the decompressed data
+ * is never held in memory as a whole. xz and zip input is decompressed
+ * into a buffer first.
+ */
+pub fn decodestream<'a, R: Read + 'a>(mut input: R) -> Result<Box<dyn Read + 'a>, String> {
+    // Read the magic bytes and put them back in front of the input
+    let mut magic = [0u8; 6];
+    let mut len = 0;
+    while len < magic.len() {
+        match input.read(&mut magic[len..]) {
+            Ok(0) => return Err("input is too short".into()),
+            Ok(n) => len += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    let mut input = Cursor::new(magic).chain(input);
+
+    let decoded: Box<dyn Read + 'a> = match magic {
+        [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00] => {
+            console_log!("xz compression detected");
+            let mut data = Vec::new();
+            input.read_to_end(&mut data).map_err(|e| e.to_string())?;
+            Box::new(Cursor::new(decodebuffer_xz(&data)?))
+        }
+        [0x28, 0xb5, 0x2f, 0xfd, _, _] => {
+            console_log!("zstd compression detected");
+            Box::new(BufReader::new(ZstdDecoder::new(input)))
+        }
+        [0x1f, 0x8b, 0x08, _, _, _] => {
+            console_log!("gz compression detected");
+            Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(input)))
+        }
+        [0x50, 0x4b, 0x03, 0x04, _, _] => {
+            console_log!("zip compression detected");
+            let mut data = Vec::new();
+            input.read_to_end(&mut data).map_err(|e| e.to_string())?;
+            Box::new(Cursor::new(decodebuffer_zip(&data, None)?))
+        }
+        _ => {
+            console_log!("No compression detected");
+            Box::new(BufReader::new(input))
+        }
+    };
+    Ok(decoded)
+}
+
+/** Streaming zstd decompressor
+ *
+ * Decodes all frames of the input one after another and skips
+ * skippable frames. Only the zstd window of the current frame is
+ * kept in memory.
+ *
+ * If a frame carries a content checksum, it is verified once the
+ * frame is fully read. Corrupt input is reported as an `io::Error`
+ * of kind `InvalidData` naming the frame index and the byte offset in
+ * the compressed input.
+ */
+pub struct ZstdDecoder<R: Read> {
+    source: CountingReader<R>,
+    frame_dec: FrameDecoder,
+    in_frame: bool,
+    /// Index and offset of the current frame in the compressed input
+    frame_index: usize,
+    frame_start: u64,
+    tracker: StateTracker,
+}
+
 struct StateTracker {
     bytes_used: u64,
     frames_used: usize,
 }
 
-pub fn decodebuffer_zstd(input: &[u8]) -> Vec<u8> {
-    let mut frame_dec = FrameDecoder::new();
+/// Buffered reader which counts the consumed bytes
+struct CountingReader<R: Read> {
+    inner: BufReader<R>,
+    count: u64,
+}
 
-    let mut tracker = StateTracker {
-        bytes_used: 0,
-        frames_used: 0,
-    };
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+}
 
-    let batch_size = 1024 * 1024 * 10;
-    let mut result = Vec::with_capacity(input.len());
+impl<R: Read> ZstdDecoder<R> {
+    pub fn new(source: R) -> ZstdDecoder<R> {
+        ZstdDecoder {
+            source: CountingReader {
+                inner: BufReader::new(source),
+                count: 0,
+            },
+            frame_dec: FrameDecoder::new(),
+            in_frame: false,
+            frame_index: 0,
+            frame_start: 0,
+            tracker: StateTracker {
+                bytes_used: 0,
+                frames_used: 0,
+            },
+        }
+    }
 
-    let mut cursor = std::io::Cursor::new(input);
-
-    while cursor.position() < input.len() as u64 {
-        match frame_dec.reset(&mut cursor) {
-            Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame(
-                _magic_num,
-                skip_size,
-            ))) => {
-                cursor
-                    .seek(std::io::SeekFrom::Current(skip_size as i64))
-                    .unwrap();
-                continue;
+    /// Start the next frame, returns false at the end of the input
+    fn next_frame(&mut self) -> std::io::Result<bool> {
+        loop {
+            if self.source.inner.fill_buf()?.is_empty() {
+                console_log!(
+                    "decoded {} bytes from {} frames",
+                    self.tracker.bytes_used,
+                    self.tracker.frames_used
+                );
+                return Ok(false);
+            }
+            self.frame_index = self.tracker.frames_used;
+            self.frame_start = self.source.count;
+            match self.frame_dec.reset(&mut self.source) {
+                Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame(
+                    _magic_num,
+                    skip_size,
+                ))) => {
+                    let skipped = std::io::copy(
+                        &mut (&mut self.source).take(skip_size as u64),
+                        &mut std::io::sink(),
+                    )?;
+                    if skipped < skip_size as u64 {
+                        return Err(invalid_data(format!(
+                            "skippable zstd frame at byte {} is truncated ({} of {} bytes)",
+                            self.frame_start, skipped, skip_size
+                        )));
+                    }
+                }
+                Err(e) => return Err(self.frame_error(e)),
+                Ok(()) => {
+                    self.tracker.frames_used += 1;
+                    return Ok(true);
+                }
             }
-            other => other.unwrap(),
         }
+    }
 
-        tracker.frames_used += 1;
-
-        while !frame_dec.is_finished() {
-            frame_dec
-                .decode_blocks(&mut cursor, BlockDecodingStrategy::UptoBytes(batch_size))
-                .unwrap();
-
-            if frame_dec.can_collect() > batch_size {
-                let x = frame_dec.read_to_end(&mut result).unwrap();
-                tracker.bytes_used += x as u64;
+    /// Compare the checksum stored in the finished frame with the decoded data
+    fn verify_checksum(&self) -> std::io::Result<()> {
+        let expected = self.frame_dec.get_checksum_from_data();
+        let calculated = self.frame_dec.get_calculated_checksum();
+        match (expected, calculated) {
+            (Some(expected), Some(calculated)) if expected != calculated => {
+                Err(invalid_data(format!(
+                    "zstd frame {} (starting at byte {}): checksum mismatch, expected {:#010x} but decoded data has {:#010x}",
+                    self.frame_index,
+                    self.frame_start,
+                    expected,
+                    calculated
+                )))
             }
+            _ => Ok(()),
         }
+    }
 
-        // handle the last chunk of data
-        while frame_dec.can_collect() > 0 {
-            let x = frame_dec.read_to_end(&mut result).unwrap();
-            tracker.bytes_used += x as u64;
+    fn frame_error(&self, error: FrameDecoderError) -> std::io::Error {
+        invalid_data(format!(
+            "zstd frame {} (starting at byte {}): {} at byte {}",
+            self.frame_index, self.frame_start, error, self.source.count
+        ))
+    }
+}
+
+impl<R: Read> Read for ZstdDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let batch_size = 1024 * 1024;
+        loop {
+            if self.in_frame && self.frame_dec.can_collect() > 0 {
+                let n = self.frame_dec.read(buf)?;
+                self.tracker.bytes_used += n as u64;
+                return Ok(n);
+            }
+            if self.in_frame && self.frame_dec.is_finished() {
+                self.verify_checksum()?;
+                self.in_frame = false;
+            }
+            if !self.in_frame {
+                self.in_frame = self.next_frame()?;
+                if !self.in_frame {
+                    return Ok(0);
+                }
+                continue;
+            }
+            if let Err(e) = self.frame_dec.decode_blocks(
+                &mut self.source,
+                BlockDecodingStrategy::UptoBytes(batch_size),
+            ) {
+                return Err(self.frame_error(e));
+            }
         }
     }
+}
 
-    result
+fn invalid_data(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+/* Decompress all zstd frames of the input
+ *
+ * Fails with a message naming the frame and the byte offset if the
+ * input is corrupt or a frame checksum does not match.
+ */
+pub fn decodebuffer_zstd(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut result = Vec::with_capacity(input.len());
+    ZstdDecoder::new(input)
+        .read_to_end(&mut result)
+        .map_err(|e| e.to_string())?;
+    Ok(result)
 }
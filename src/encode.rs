@@ -0,0 +1,7 @@
+/* Compress a buffer with zstd
+ *
+ * `level` is the zstd compression level, 0 selects the default level.
+ */
+pub fn encodebuffer_zstd(input: &[u8], level: i32) -> Result<Vec<u8>, String> {
+    zstd::bulk::compress(input, level).map_err(|e| format!("zstd compression failed: {}", e))
+}
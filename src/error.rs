@@ -0,0 +1,208 @@
+use std::fmt;
+
+use wasm_bindgen::JsValue;
+
+/** The part of a gt file that was being parsed
+ * when an error occurred.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Section {
+    Header,
+    Comment,
+    Adjacency,
+    PropertyHeader,
+    /// The payload of the property with the given name
+    Property(String),
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Section::Header => write!(f, "header"),
+            Section::Comment => write!(f, "comment"),
+            Section::Adjacency => write!(f, "adjacency"),
+            Section::PropertyHeader => write!(f, "property header"),
+            Section::Property(name) => write!(f, "property '{}'", name),
+        }
+    }
+}
+
+/** Errors that can occur while loading a graph.
+ *
+ * Parsing errors carry the byte offset (in the decompressed file)
+ * and the section that was being parsed.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum GtError {
+    /// The input ended before the section was complete
+    Truncated {
+        offset: u64,
+        section: Section,
+    },
+    /// The file does not start with the gt magic string
+    BadMagic {
+        offset: u64,
+        found: Vec<u8>,
+    },
+    UnsupportedVersion {
+        offset: u64,
+        version: u8,
+    },
+    UnsupportedEndianness {
+        offset: u64,
+        endianness: u8,
+    },
+    UnknownPropertyMapType {
+        offset: u64,
+        section: Section,
+        value: u8,
+    },
+    UnknownPropertyType {
+        offset: u64,
+        section: Section,
+        value: u8,
+    },
+    InvalidUtf8 {
+        offset: u64,
+        section: Section,
+    },
+    /// An adjacency list references a vertex that does not exist
+    NeighborOutOfRange {
+        offset: u64,
+        vertex: u64,
+        neighbor: u64,
+        num_vertices: u64,
+    },
+    /// The (compressed) input could not be decoded
+    Decode(String),
+}
+
+impl GtError {
+    /// Short machine readable name of the error variant
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GtError::Truncated { .. } => "Truncated",
+            GtError::BadMagic { .. } => "BadMagic",
+            GtError::UnsupportedVersion { .. } => "UnsupportedVersion",
+            GtError::UnsupportedEndianness { .. } => "UnsupportedEndianness",
+            GtError::UnknownPropertyMapType { .. } => "UnknownPropertyMapType",
+            GtError::UnknownPropertyType { .. } => "UnknownPropertyType",
+            GtError::InvalidUtf8 { .. } => "InvalidUtf8",
+            GtError::NeighborOutOfRange { .. } => "NeighborOutOfRange",
+            GtError::Decode(_) => "Decode",
+        }
+    }
+
+    /// Byte offset at which the error occurred (if known)
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            GtError::Truncated { offset, .. }
+            | GtError::BadMagic { offset, .. }
+            | GtError::UnsupportedVersion { offset, .. }
+            | GtError::UnsupportedEndianness { offset, .. }
+            | GtError::UnknownPropertyMapType { offset, .. }
+            | GtError::UnknownPropertyType { offset, .. }
+            | GtError::InvalidUtf8 { offset, .. }
+            | GtError::NeighborOutOfRange { offset, .. } => Some(*offset),
+            GtError::Decode(_) => None,
+        }
+    }
+
+    /// Section of the file that was parsed (if known)
+    pub fn section(&self) -> Option<Section> {
+        match self {
+            GtError::Truncated { section, .. }
+            | GtError::UnknownPropertyMapType { section, .. }
+            | GtError::UnknownPropertyType { section, .. }
+            | GtError::InvalidUtf8 { section, .. } => Some(section.clone()),
+            GtError::BadMagic { .. }
+            | GtError::UnsupportedVersion { .. }
+            | GtError::UnsupportedEndianness { .. } => Some(Section::Header),
+            GtError::NeighborOutOfRange { .. } => Some(Section::Adjacency),
+            GtError::Decode(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for GtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GtError::Truncated { offset, section } => {
+                write!(
+                    f,
+                    "unexpected end of file in {} at byte {}",
+                    section, offset
+                )
+            }
+            GtError::BadMagic { offset, found } => {
+                write!(f, "invalid magic string {:02x?} at byte {}", found, offset)
+            }
+            GtError::UnsupportedVersion { offset, version } => {
+                write!(f, "unsupported gt version {} at byte {}", version, offset)
+            }
+            GtError::UnsupportedEndianness { offset, endianness } => write!(
+                f,
+                "unsupported endianness {:#04x} at byte {}",
+                endianness, offset
+            ),
+            GtError::UnknownPropertyMapType {
+                offset,
+                section,
+                value,
+            } => write!(
+                f,
+                "unknown property map type {:#04x} in {} at byte {}",
+                value, section, offset
+            ),
+            GtError::UnknownPropertyType {
+                offset,
+                section,
+                value,
+            } => write!(
+                f,
+                "unknown property value type {:#04x} in {} at byte {}",
+                value, section, offset
+            ),
+            GtError::InvalidUtf8 { offset, section } => {
+                write!(f, "invalid utf-8 string in {} at byte {}", section, offset)
+            }
+            GtError::NeighborOutOfRange {
+                offset,
+                vertex,
+                neighbor,
+                num_vertices,
+            } => write!(
+                f,
+                "vertex {} has neighbor {} but the graph only has {} vertices (byte {})",
+                vertex, neighbor, num_vertices, offset
+            ),
+            GtError::Decode(msg) => write!(f, "could not decode input: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GtError {}
+
+/// Converts the error into a JavaScript `Error` named `GtError`
+/// with additional `kind`, `offset` and `section` fields.
+impl From<GtError> for JsValue {
+    fn from(error: GtError) -> Self {
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name("GtError");
+
+        let offset = match error.offset() {
+            Some(offset) => JsValue::from_f64(offset as f64),
+            None => JsValue::NULL,
+        };
+        let section = match error.section() {
+            Some(section) => JsValue::from_str(&section.to_string()),
+            None => JsValue::NULL,
+        };
+        // Setting fields on a fresh error object can not fail
+        let _ = js_sys::Reflect::set(&js_error, &"kind".into(), &error.kind().into());
+        let _ = js_sys::Reflect::set(&js_error, &"offset".into(), &offset);
+        let _ = js_sys::Reflect::set(&js_error, &"section".into(), &section);
+
+        js_error.into()
+    }
+}
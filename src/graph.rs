@@ -1,21 +1,36 @@
+pub mod centrality;
+pub mod clustering;
+pub mod components;
+pub mod edit;
 pub mod io;
 pub mod properties;
+pub mod shortest_paths;
+pub mod traversal;
 
-use crate::graph_file::GraphFile;
+use std::cell::OnceCell;
+
+use crate::graph_file::{Adjacency, EdgeIter, GraphFile, Incidence, Neighbors};
 
 /** The Graph struct represents a graph and provides a number
  * of methods to access the data.
+ *
+ * Neighbor queries respect the direction of the graph. In undirected
+ * graphs every edge connects both endpoints: the out neighbors of a
+ * node are the targets of the edges stored with it followed by the
+ * sources of the edges pointing to it, and a self-loop is listed (and
+ * counted in the degree) twice, as in graph-tool.
  */
-
+#[derive(Default, Debug, Clone)]
 pub struct Graph {
     file: GraphFile,
+    /// In neighbors (directed) or all neighbors (undirected) of every node,
+    /// built on first use
+    incidence: OnceCell<Incidence>,
 }
 
 impl Graph {
     pub fn new() -> Graph {
-        Graph {
-            file: GraphFile::default(),
-        }
+        Graph::default()
     }
 
     pub fn num_vertices(&self) -> u64 {
@@ -30,22 +45,185 @@ impl Graph {
         self.file.directed
     }
 
+    /// Version of the gt format the graph was read from
+    pub fn version(&self) -> u8 {
+        self.file.version()
+    }
+
+    /// Byte order of the file: 0x00 (little endian) or 0x01 (big endian)
+    pub fn endianness(&self) -> u8 {
+        self.file.endianness()
+    }
+
+    /// Comment of the gt file
+    ///
+    /// Files from graph-tool name the program which wrote them, files from
+    /// netzschleuder often tell where the network comes from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.comment().starts_with("graph-tool binary file"));
+    ///
+    /// graph.set_comment("my graph".to_string());
+    /// let copy = Graph::try_from(graph.to_bytes()).unwrap();
+    /// assert_eq!(copy.comment(), "my graph");
+    /// ```
+    pub fn comment(&self) -> &str {
+        self.file.comment()
+    }
+
+    /// Replace the comment which is written when the graph is serialized
+    pub fn set_comment(&mut self, comment: String) {
+        self.file.set_comment(comment);
+    }
+
     pub fn vertices(&self) -> Vec<u64> {
         (0..self.file.num_vertices).collect()
     }
 
+    /// Get the sources and targets of all edges in edge index order
+    ///
+    /// Every edge is listed once, also in undirected graphs.
     pub fn edges(&self) -> (Vec<u64>, Vec<u64>) {
-        //from to pairs
-        self.file
-            .out_neighbors
-            .iter()
-            .enumerate()
-            .flat_map(|(from, to)| to.iter().map(move |&to| (from as u64, to)))
-            .unzip()
+        let adjacency = &self.file.out_neighbors;
+        let mut from = vec![0; adjacency.num_edges() as usize];
+        let mut to = vec![0; adjacency.num_edges() as usize];
+        for source in 0..adjacency.num_vertices() {
+            for (edge, target) in adjacency.edges(source).zip(adjacency.get(source)) {
+                from[edge as usize] = source;
+                to[edge as usize] = target;
+            }
+        }
+        (from, to)
+    }
+
+    /// Get the source and target of an edge
+    ///
+    /// Edges are indexed in the order of the file, which is also the order
+    /// of the values in edge properties. Added edges are numbered on from
+    /// there (see `Graph::add_edge`). Returns `None` if there is no edge
+    /// with this index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the edge
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let (source, target) = graph.edge(0).unwrap();
+    /// assert!(graph.out_neighbors(source).contains(target));
+    /// ```
+    pub fn edge(&self, index: u64) -> Option<(u64, u64)> {
+        self.file.out_neighbors.edge(index)
+    }
+
+    /// Get the indices of all edges from `source` to `target`
+    ///
+    /// There may be more than one edge between two nodes (multi-edges).
+    /// For undirected graphs the direction of the edges is ignored. The
+    /// indices are sorted.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source node
+    /// * `target` - The target node
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let (source, target) = graph.edge(3).unwrap();
+    /// assert!(graph.edge_index(source, target).contains(&3));
+    /// ```
+    pub fn edge_index(&self, source: u64, target: u64) -> Vec<u64> {
+        let mut indices: Vec<u64> = self
+            .out_edges(source)
+            .zip(self.out_neighbors(source))
+            .filter(|&(_, t)| t == target)
+            .map(|(edge, _)| edge)
+            .collect();
+        // Undirected self-loops are listed twice
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    /// Get the indices of the out edges of a node, in the order of `out_neighbors`
+    ///
+    /// In directed graphs the out edges of a node have consecutive indices,
+    /// unless edges were added to the graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// // The network is undirected, so the edge may be stored in either direction
+    /// for (edge, target) in graph.out_edges(0).zip(graph.out_neighbors(0)) {
+    ///     let (s, t) = graph.edge(edge).unwrap();
+    ///     assert!((s, t) == (0, target) || (s, t) == (target, 0));
+    /// }
+    /// ```
+    pub fn out_edges(&self, node: u64) -> EdgeIter<'_> {
+        match self.file.directed {
+            true => self.file.out_neighbors.edges(node),
+            false => EdgeIter::Slice(self.incidence().edges(node).iter()),
+        }
+    }
+
+    /// Get the indices of the in edges of a node, in the order of `in_neighbors`
+    ///
+    /// For undirected graphs these are the out edges.
+    pub fn in_edges(&self, node: u64) -> EdgeIter<'_> {
+        match self.file.directed {
+            true => EdgeIter::Slice(self.incidence().edges(node).iter()),
+            false => self.out_edges(node),
+        }
+    }
+
+    /// The out-neighbors of all nodes in compressed sparse row layout,
+    /// as stored in the file
+    ///
+    /// Every edge is stored once, with its source. For undirected graphs
+    /// use `out_neighbors` to get all neighbors of a node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let adjacency = graph.adjacency();
+    /// assert_eq!(adjacency.offsets().len() as u64, graph.num_vertices() + 1);
+    /// assert_eq!(adjacency.targets().len() as u64, graph.num_edges());
+    /// ```
+    pub fn adjacency(&self) -> &Adjacency {
+        &self.file.out_neighbors
     }
 
     /// Get the out neighbors of a node
     ///
+    /// For undirected graphs these are all neighbors of the node.
+    ///
+    /// Returns `Neighbors` rather than a slice, since the indices are stored
+    /// as `u32` if there are at most 2^32 vertices (use `Neighbors::to_vec`
+    /// for a `Vec<u64>`).
+    ///
     /// # Arguments
     ///
     /// * `node` - The node for which to get the out neighbors
@@ -53,17 +231,41 @@ impl Graph {
     /// # Example
     ///
     /// ```
-    /// use gt_graph_wasm::graph::Graph;
+    /// use gt_graph_wasm::Graph;
     ///
-    /// let graph = Graph::new();
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
     /// let out_neighbors = graph.out_neighbors(0);
+    /// for neighbor in out_neighbors {
+    ///     assert!(neighbor < graph.num_vertices());
+    /// }
     /// ```
-    pub fn out_neighbors(&self, node: u64) -> &[u64] {
-        self.file.out_neighbors[node as usize].as_slice()
+    pub fn out_neighbors(&self, node: u64) -> Neighbors<'_> {
+        match self.file.directed {
+            true => self.file.out_neighbors.get(node),
+            false => self.incidence().neighbors(node),
+        }
+    }
+
+    /// Get the number of out neighbors of a node
+    ///
+    /// For undirected graphs this is the degree of the node.
+    pub fn out_degree(&self, node: u64) -> u64 {
+        match self.file.directed {
+            true => self.file.out_neighbors.degree(node),
+            false => self.incidence().degree(node),
+        }
     }
 
     /// Get the in neighbors of a node
     ///
+    /// The reverse adjacency is built once on the first call, so every
+    /// query afterwards is a slice lookup. The in neighbors are ordered
+    /// by source (by edge index in a graph read from a file).
+    ///
+    /// For undirected graphs these are the out neighbors, i.e. all
+    /// neighbors of the node.
+    ///
     /// # Arguments
     ///
     /// * `node` - The node for which to get the in neighbors
@@ -71,26 +273,55 @@ impl Graph {
     /// # Example
     ///
     /// ```
-    /// use gt_graph_wasm::graph::Graph;
+    /// use gt_graph_wasm::Graph;
     ///
-    /// let graph = Graph::new();
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
     /// let in_neighbors = graph.in_neighbors(0);
     /// ```
-    pub fn in_neighbors(&self, node: u64) -> Vec<u64> {
-        let mut in_neighbors: Vec<u64> = Vec::new();
-        for (i, neighbors) in self.file.out_neighbors.iter().enumerate() {
-            if neighbors.contains(&node) {
-                in_neighbors.push(i as u64);
-            }
+    pub fn in_neighbors(&self, node: u64) -> Neighbors<'_> {
+        if !self.file.directed {
+            return self.out_neighbors(node);
         }
-        in_neighbors
+        self.incidence().neighbors(node)
     }
-}
 
-impl Default for Graph {
-    fn default() -> Self {
-        Graph {
-            file: GraphFile::default(),
+    /// Get the number of in neighbors of a node
+    ///
+    /// For undirected graphs this is the out degree.
+    pub fn in_degree(&self, node: u64) -> u64 {
+        if !self.file.directed {
+            return self.out_degree(node);
         }
+        self.incidence().degree(node)
+    }
+
+    /// Get the out neighbors followed by the in neighbors of a node
+    ///
+    /// For undirected graphs these are the out neighbors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let neighbors: Vec<u64> = graph.all_neighbors(0).collect();
+    /// ```
+    pub fn all_neighbors(&self, node: u64) -> impl Iterator<Item = u64> + '_ {
+        let in_neighbors = match self.file.directed {
+            true => self.in_neighbors(node),
+            false => Neighbors::U64(&[]),
+        };
+        self.out_neighbors(node).iter().chain(in_neighbors.iter())
+    }
+
+    /// The in neighbors (directed) or all neighbors (undirected) of all nodes
+    fn incidence(&self) -> &Incidence {
+        self.incidence.get_or_init(|| match self.file.directed {
+            true => Incidence::reversed(&self.file.out_neighbors),
+            false => Incidence::undirected(&self.file.out_neighbors),
+        })
     }
 }
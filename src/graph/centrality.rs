@@ -0,0 +1,600 @@
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::graph_file::properties::{PropertyMapType, PropertyType};
+use crate::shortest_paths::Candidate;
+use crate::traversal::UNREACHABLE;
+use crate::Graph;
+
+/// Iterative measures stop when the values change by less than this (in sum)
+pub const EPSILON: f64 = 1e-6;
+
+/// Iterative measures fail if they don't converge within this many iterations
+pub const MAX_ITERATIONS: usize = 10_000;
+
+/// Relative tolerance within which two path lengths count as equal
+const DISTANCE_EPSILON: f64 = 1e-10;
+
+/// Options for `Graph::pagerank`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRankOptions {
+    /// Probability to follow an edge instead of jumping to a random vertex
+    pub damping: f64,
+    /// Name of a numeric vertex property with the probabilities to jump to
+    /// each vertex (normalized to sum one), all vertices alike if `None`
+    pub personalization: Option<String>,
+    /// Name of a numeric edge property with the weights, 1 if `None`
+    pub weight: Option<String>,
+}
+
+impl Default for PageRankOptions {
+    fn default() -> Self {
+        PageRankOptions {
+            damping: 0.85,
+            personalization: None,
+            weight: None,
+        }
+    }
+}
+
+/// Options for `Graph::betweenness`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetweennessOptions {
+    /// Name of a numeric edge property with the lengths of the edges,
+    /// 1 if `None`
+    pub weight: Option<String>,
+    /// Divide by the number of pairs of (other) vertices, as in graph-tool
+    pub normalized: bool,
+}
+
+impl Default for BetweennessOptions {
+    fn default() -> Self {
+        BetweennessOptions {
+            weight: None,
+            normalized: true,
+        }
+    }
+}
+
+/// Options for `Graph::closeness`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosenessOptions {
+    /// Name of a numeric edge property with the lengths of the edges,
+    /// 1 if `None`
+    pub weight: Option<String>,
+    /// Sum the inverse distances instead of inverting the sum of distances
+    pub harmonic: bool,
+    /// Multiply by the number of reachable vertices (divide by the number
+    /// of other vertices for harmonic centrality), as in graph-tool
+    pub normalized: bool,
+}
+
+impl Default for ClosenessOptions {
+    fn default() -> Self {
+        ClosenessOptions {
+            weight: None,
+            harmonic: false,
+            normalized: true,
+        }
+    }
+}
+
+/// Options for `Graph::katz`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KatzOptions {
+    /// Attenuation factor, has to be smaller than the inverse of the largest
+    /// eigenvalue of the adjacency matrix
+    pub alpha: f64,
+    /// Name of a numeric vertex property with the centrality every vertex
+    /// gets on its own, 1 if `None`
+    pub beta: Option<String>,
+    /// Name of a numeric edge property with the weights, 1 if `None`
+    pub weight: Option<String>,
+    /// Scale the values to unit length
+    pub normalized: bool,
+}
+
+impl Default for KatzOptions {
+    fn default() -> Self {
+        KatzOptions {
+            alpha: 0.01,
+            beta: None,
+            weight: None,
+            normalized: true,
+        }
+    }
+}
+
+/// Centrality measures, as in graph-tool's `centrality` module.
+///
+/// The results are stored as new `double` vertex (or edge) properties with
+/// the given names, replacing existing properties of the same name, so they
+/// are saved with the graph. In undirected graphs every edge is followed in
+/// both directions.
+impl Graph {
+    /// PageRank of every vertex
+    ///
+    /// The values sum to one. Vertices without out edges jump according to
+    /// the personalization (or uniformly).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a property does not exist or is not numeric, the
+    /// weights have negative values, the personalization has negative values
+    /// or sums to zero, or the iteration does not converge
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::centrality::PageRankOptions;
+    /// use gt_graph_wasm::graph_file::properties::PropertyMapType;
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let mut graph = Graph::try_from(data).unwrap();
+    /// graph.pagerank("pagerank", &PageRankOptions::default()).unwrap();
+    ///
+    /// let pagerank = graph.property_ref("pagerank", Some(PropertyMapType::Vertex)).unwrap();
+    /// let sum: f64 = pagerank.as_f64_slice().unwrap().iter().sum();
+    /// assert!((sum - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn pagerank(&mut self, name: &str, options: &PageRankOptions) -> Result<(), String> {
+        let n = self.num_vertices() as usize;
+        let weights = self.edge_weights(options.weight.as_deref())?;
+        check_non_negative(&weights)?;
+        let jump = match &options.personalization {
+            Some(personalization) => {
+                let values = self.numeric_values(personalization, PropertyMapType::Vertex)?;
+                let sum: f64 = values.iter().sum();
+                if values.iter().any(|p| *p < 0.0) || sum.is_nan() || sum <= 0.0 {
+                    return Err(format!(
+                        "Personalization '{}' has to be non-negative with a positive sum",
+                        personalization
+                    ));
+                }
+                values.iter().map(|p| p / sum).collect()
+            }
+            None => vec![1.0 / n as f64; n],
+        };
+        let out_weight: Vec<f64> = (0..self.num_vertices())
+            .map(|vertex| self.out_edges(vertex).map(|e| weights[e as usize]).sum())
+            .collect();
+
+        let damping = options.damping;
+        let ranks = converge("PageRank", jump.clone(), |rank| {
+            let dangling: f64 = (0..n)
+                .filter(|&vertex| out_weight[vertex] == 0.0)
+                .map(|vertex| rank[vertex])
+                .sum();
+            let mut next: Vec<f64> = jump
+                .iter()
+                .map(|p| (1.0 - damping) * p + damping * p * dangling)
+                .collect();
+            for vertex in 0..self.num_vertices() {
+                if out_weight[vertex as usize] == 0.0 {
+                    continue;
+                }
+                let share = damping * rank[vertex as usize] / out_weight[vertex as usize];
+                for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                    next[target as usize] += share * weights[edge as usize];
+                }
+            }
+            next
+        })?;
+        self.store(name, PropertyMapType::Vertex, ranks)
+    }
+
+    /// Betweenness of every vertex and every edge (Brandes' algorithm)
+    ///
+    /// The betweenness of a vertex (edge) is the fraction of shortest paths
+    /// between pairs of other vertices which pass through it, summed over all
+    /// pairs. Pairs are unordered in undirected graphs.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_name` - Name of the new vertex property
+    /// * `edge_name` - Name of the new edge property
+    /// * `options` - The weights and whether to normalize
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the weight property does not exist, is not numeric
+    /// or has negative values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::centrality::BetweennessOptions;
+    /// use gt_graph_wasm::graph_file::properties::PropertyMapType;
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// // A path 0 - 1 - 2, every path between the ends passes vertex 1
+    /// let mut graph = Graph::new();
+    /// for _ in 0..3 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    /// graph.betweenness("vb", "eb", &BetweennessOptions::default()).unwrap();
+    ///
+    /// let vb = graph.property_ref("vb", Some(PropertyMapType::Vertex)).unwrap();
+    /// assert_eq!(vb.as_f64_slice().unwrap(), &[0.0, 1.0, 0.0]);
+    /// ```
+    pub fn betweenness(
+        &mut self,
+        vertex_name: &str,
+        edge_name: &str,
+        options: &BetweennessOptions,
+    ) -> Result<(), String> {
+        let n = self.num_vertices() as usize;
+        let weighted = options.weight.is_some();
+        let weights = self.edge_weights(options.weight.as_deref())?;
+        check_non_negative(&weights)?;
+        let mut vertex_values = vec![0.0; n];
+        let mut edge_values = vec![0.0; self.num_edges() as usize];
+
+        for source in 0..self.num_vertices() {
+            // The vertices by increasing distance, with the number of shortest
+            // paths to them and the last edges of these paths
+            let order = match weighted {
+                true => self.weighted_shortest_path_dag(source, &weights),
+                false => self.shortest_path_dag(source),
+            };
+            let mut dependency = vec![0.0; n];
+            for &vertex in order.vertices.iter().rev() {
+                let v = vertex as usize;
+                for &(predecessor, edge) in &order.predecessors[v] {
+                    let p = predecessor as usize;
+                    let share = order.paths[p] / order.paths[v] * (1.0 + dependency[v]);
+                    edge_values[edge as usize] += share;
+                    dependency[p] += share;
+                }
+                if vertex != source {
+                    vertex_values[v] += dependency[v];
+                }
+            }
+        }
+
+        // Every unordered pair was counted from both ends
+        let mut vertex_factor = if self.directed() { 1.0 } else { 0.5 };
+        let mut edge_factor = vertex_factor;
+        if options.normalized {
+            let pairs = if self.directed() { 1.0 } else { 2.0 };
+            if n > 2 {
+                vertex_factor *= pairs / ((n - 1) * (n - 2)) as f64;
+            }
+            if n > 1 {
+                edge_factor *= pairs / (n * (n - 1)) as f64;
+            }
+        }
+        vertex_values.iter_mut().for_each(|v| *v *= vertex_factor);
+        edge_values.iter_mut().for_each(|e| *e *= edge_factor);
+
+        self.store(vertex_name, PropertyMapType::Vertex, vertex_values)?;
+        self.store(edge_name, PropertyMapType::Edge, edge_values)
+    }
+
+    /// Closeness (or harmonic) centrality of every vertex
+    ///
+    /// The closeness is the inverse of the sum of the distances to all
+    /// reachable vertices, the harmonic centrality the sum of the inverse
+    /// distances. As in graph-tool, vertices which can't reach any other
+    /// vertex have the closeness NaN.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the weight property does not exist, is not numeric
+    /// or has negative values
+    pub fn closeness(&mut self, name: &str, options: &ClosenessOptions) -> Result<(), String> {
+        let n = self.num_vertices() as usize;
+        let weights = match &options.weight {
+            Some(weight) => {
+                let weights = self.edge_weights(Some(weight))?;
+                check_non_negative(&weights)?;
+                Some(weights)
+            }
+            None => None,
+        };
+
+        let values = (0..self.num_vertices())
+            .map(|vertex| {
+                let distances: Vec<f64> = match &weights {
+                    Some(weights) => self.best_first(vertex, None, weights, |_| 0.0).distances,
+                    None => self
+                        .bfs(vertex)
+                        .unwrap()
+                        .distances
+                        .iter()
+                        .map(|&d| {
+                            if d == UNREACHABLE {
+                                f64::INFINITY
+                            } else {
+                                d as f64
+                            }
+                        })
+                        .collect(),
+                };
+                let mut sum = 0.0;
+                let mut reached = 0;
+                for (other, &distance) in distances.iter().enumerate() {
+                    if other == vertex as usize || distance == f64::INFINITY {
+                        continue;
+                    }
+                    sum += if options.harmonic {
+                        1.0 / distance
+                    } else {
+                        distance
+                    };
+                    reached += 1;
+                }
+                match (options.harmonic, options.normalized) {
+                    (true, true) if n > 1 => sum / (n - 1) as f64,
+                    (true, _) => sum,
+                    (false, _) if reached == 0 => f64::NAN,
+                    (false, true) => reached as f64 / sum,
+                    (false, false) => 1.0 / sum,
+                }
+            })
+            .collect();
+        self.store(name, PropertyMapType::Vertex, values)
+    }
+
+    /// Eigenvector centrality of every vertex, returns the largest eigenvalue
+    /// of the (weighted) adjacency matrix
+    ///
+    /// The centrality of a vertex is proportional to the sum of the
+    /// centralities of its in neighbors. The values have unit length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the weight property does not exist or is not
+    /// numeric, or the iteration does not converge
+    pub fn eigenvector(&mut self, name: &str, weight: Option<&str>) -> Result<f64, String> {
+        let n = self.num_vertices() as usize;
+        let weights = self.edge_weights(weight)?;
+        let mut eigenvalue = 0.0;
+        // Iterating with A + I instead of A has the same eigenvectors, but
+        // doesn't oscillate in bipartite graphs
+        let start = vec![1.0 / (n as f64).sqrt(); n];
+        let values = converge("Eigenvector centrality", start, |x| {
+            let mut next = self.follow_out_edges(x, &weights);
+            next.iter_mut().zip(x).for_each(|(y, x)| *y += x);
+            eigenvalue = normalize(&mut next) - 1.0;
+            next
+        })?;
+        self.store(name, PropertyMapType::Vertex, values)?;
+        Ok(eigenvalue)
+    }
+
+    /// Katz centrality of every vertex
+    ///
+    /// The centrality of a vertex is `alpha` times the sum of the centralities
+    /// of its in neighbors plus its own `beta`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a property does not exist or is not numeric, or the
+    /// iteration does not converge (if `alpha` is too large)
+    pub fn katz(&mut self, name: &str, options: &KatzOptions) -> Result<(), String> {
+        let n = self.num_vertices() as usize;
+        let weights = self.edge_weights(options.weight.as_deref())?;
+        let beta = match &options.beta {
+            Some(beta) => self.numeric_values(beta, PropertyMapType::Vertex)?,
+            None => vec![1.0; n],
+        };
+        let mut values = converge("Katz centrality", beta.clone(), |x| {
+            let mut next = self.follow_out_edges(x, &weights);
+            next.iter_mut()
+                .zip(&beta)
+                .for_each(|(y, b)| *y = options.alpha * *y + b);
+            next
+        })?;
+        if options.normalized {
+            normalize(&mut values);
+        }
+        self.store(name, PropertyMapType::Vertex, values)
+    }
+
+    /// HITS authority and hub centrality of every vertex, returns the largest
+    /// eigenvalue of the cocitation matrix
+    ///
+    /// The authority of a vertex is proportional to the sum of the hub values
+    /// of its in neighbors, its hub value to the sum of the authorities of its
+    /// out neighbors. Both have unit length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the weight property does not exist or is not
+    /// numeric, or the iteration does not converge
+    pub fn hits(
+        &mut self,
+        authority_name: &str,
+        hub_name: &str,
+        weight: Option<&str>,
+    ) -> Result<f64, String> {
+        let n = self.num_vertices() as usize;
+        let weights = self.edge_weights(weight)?;
+        let mut eigenvalue = 0.0;
+        let mut authorities = Vec::new();
+        let start = vec![1.0 / (n as f64).sqrt(); n];
+        let hubs = converge("HITS", start, |hub| {
+            authorities = self.follow_out_edges(hub, &weights);
+            let authority_norm = normalize(&mut authorities);
+            let mut next = self.collect_out_edges(&authorities, &weights);
+            eigenvalue = authority_norm * normalize(&mut next);
+            next
+        })?;
+        if authorities.is_empty() {
+            authorities = vec![0.0; n];
+        }
+        self.store(authority_name, PropertyMapType::Vertex, authorities)?;
+        self.store(hub_name, PropertyMapType::Vertex, hubs)?;
+        Ok(eigenvalue)
+    }
+
+    /// Store the values as a new double property
+    fn store(
+        &mut self,
+        name: &str,
+        map_type: PropertyMapType,
+        values: Vec<f64>,
+    ) -> Result<(), String> {
+        self.new_property(name, map_type, PropertyType::Double)
+            .set(values)
+    }
+
+    /// For every vertex the weighted sum of the values of its in neighbors
+    fn follow_out_edges(&self, values: &[f64], weights: &[f64]) -> Vec<f64> {
+        let mut sums = vec![0.0; values.len()];
+        for vertex in 0..self.num_vertices() {
+            let value = values[vertex as usize];
+            for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                sums[target as usize] += weights[edge as usize] * value;
+            }
+        }
+        sums
+    }
+
+    /// For every vertex the weighted sum of the values of its out neighbors
+    fn collect_out_edges(&self, values: &[f64], weights: &[f64]) -> Vec<f64> {
+        (0..self.num_vertices())
+            .map(|vertex| {
+                self.out_edges(vertex)
+                    .zip(self.out_neighbors(vertex))
+                    .map(|(edge, target)| weights[edge as usize] * values[target as usize])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// The shortest paths from `source` counting edges (breadth-first search)
+    fn shortest_path_dag(&self, source: u64) -> PathDag {
+        let mut dag = PathDag::new(self.num_vertices(), source);
+        let mut distances = vec![u64::MAX; self.num_vertices() as usize];
+        distances[source as usize] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(vertex) = queue.pop_front() {
+            dag.vertices.push(vertex);
+            let distance = distances[vertex as usize] + 1;
+            for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                let t = target as usize;
+                if distances[t] == u64::MAX {
+                    distances[t] = distance;
+                    queue.push_back(target);
+                }
+                if distances[t] == distance {
+                    dag.paths[t] += dag.paths[vertex as usize];
+                    dag.predecessors[t].push((vertex, edge));
+                }
+            }
+        }
+        dag
+    }
+
+    /// The shortest paths from `source` with weighted edges (Dijkstra)
+    ///
+    /// Vertices enter the DAG in the order they are settled. Only edges to
+    /// vertices which are not settled yet are followed, so zero-weight edges
+    /// between vertices at the same distance count in one direction only.
+    fn weighted_shortest_path_dag(&self, source: u64, weights: &[f64]) -> PathDag {
+        let n = self.num_vertices() as usize;
+        let mut dag = PathDag::new(self.num_vertices(), source);
+        let mut distances = vec![f64::INFINITY; n];
+        let mut settled = vec![false; n];
+        distances[source as usize] = 0.0;
+        let mut queue = BinaryHeap::from([Candidate(0.0, source)]);
+
+        while let Some(Candidate(_, vertex)) = queue.pop() {
+            let v = vertex as usize;
+            if settled[v] {
+                continue;
+            }
+            settled[v] = true;
+            dag.vertices.push(vertex);
+            for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                let t = target as usize;
+                if settled[t] {
+                    continue;
+                }
+                let distance = distances[v] + weights[edge as usize];
+                if same_distance(distance, distances[t]) {
+                    dag.paths[t] += dag.paths[v];
+                    dag.predecessors[t].push((vertex, edge));
+                } else if distance < distances[t] {
+                    distances[t] = distance;
+                    dag.paths[t] = dag.paths[v];
+                    dag.predecessors[t] = vec![(vertex, edge)];
+                    queue.push(Candidate(distance, target));
+                }
+            }
+        }
+        dag
+    }
+}
+
+/** The shortest paths from one source, see `Graph::betweenness` */
+struct PathDag {
+    /// The reached vertices, by increasing distance
+    vertices: Vec<u64>,
+    /// The number of shortest paths to every vertex
+    paths: Vec<f64>,
+    /// The previous vertices and edges on the shortest paths to every vertex
+    predecessors: Vec<Vec<(u64, u64)>>,
+}
+
+impl PathDag {
+    fn new(num_vertices: u64, source: u64) -> PathDag {
+        let mut paths = vec![0.0; num_vertices as usize];
+        paths[source as usize] = 1.0;
+        PathDag {
+            vertices: Vec::new(),
+            paths,
+            predecessors: vec![Vec::new(); num_vertices as usize],
+        }
+    }
+}
+
+/// Apply `step` until the values change by less than `EPSILON`
+fn converge(
+    measure: &str,
+    mut values: Vec<f64>,
+    mut step: impl FnMut(&[f64]) -> Vec<f64>,
+) -> Result<Vec<f64>, String> {
+    for _ in 0..MAX_ITERATIONS {
+        let next = step(&values);
+        let change: f64 = next.iter().zip(&values).map(|(a, b)| (a - b).abs()).sum();
+        values = next;
+        if change < EPSILON {
+            return Ok(values);
+        }
+    }
+    Err(format!(
+        "{} did not converge within {} iterations",
+        measure, MAX_ITERATIONS
+    ))
+}
+
+/// Scale the values to unit length (unless they are all zero), returns the length
+fn normalize(values: &mut [f64]) -> f64 {
+    let norm = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        values.iter_mut().for_each(|v| *v /= norm);
+    }
+    norm
+}
+
+/// Whether two path lengths are equal up to rounding, relative to their size
+/// as in graph-tool (e.g. 0.1 + 0.2 and 0.3)
+fn same_distance(a: f64, b: f64) -> bool {
+    let scale = a.abs().max(b.abs());
+    scale.is_finite() && (a - b).abs() <= DISTANCE_EPSILON * scale
+}
+
+fn check_non_negative(weights: &[f64]) -> Result<(), String> {
+    match weights.iter().position(|w| *w < 0.0) {
+        Some(edge) => Err(format!(
+            "Edge {} has the negative weight {}",
+            edge, weights[edge]
+        )),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,221 @@
+use crate::Graph;
+
+/// Clustering coefficients and k-cores, as graph-tool's `local_clustering`,
+/// `global_clustering` and `kcore_decomposition`.
+///
+/// In directed graphs the neighbors of a vertex are its out neighbors and
+/// every edge between two of them closes a triangle, in undirected graphs
+/// every edge between two neighbors is one triangle. Self-loops and parallel
+/// edges are ignored.
+impl Graph {
+    /// The number of triangles of every vertex
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..4 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    /// graph.add_edge(2, 0).unwrap();
+    /// graph.add_edge(2, 3).unwrap();
+    ///
+    /// assert_eq!(graph.triangles(), vec![1, 1, 1, 0]);
+    /// ```
+    pub fn triangles(&self) -> Vec<u64> {
+        self.triangles_and_pairs().0
+    }
+
+    /// The local clustering coefficient of every vertex: the fraction of
+    /// pairs of neighbors which are connected, 0 for vertices with less than
+    /// two neighbors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let clustering = graph.local_clustering();
+    /// assert_eq!(clustering.len(), 77);
+    /// assert!(clustering.iter().all(|c| (0.0..=1.0).contains(c)));
+    /// ```
+    pub fn local_clustering(&self) -> Vec<f64> {
+        let (triangles, pairs) = self.triangles_and_pairs();
+        triangles
+            .iter()
+            .zip(&pairs)
+            .map(|(&t, &p)| if p == 0 { 0.0 } else { t as f64 / p as f64 })
+            .collect()
+    }
+
+    /// The global clustering coefficient (the fraction of connected pairs of
+    /// neighbors over all vertices) and its jackknife standard deviation
+    pub fn global_clustering(&self) -> (f64, f64) {
+        let (triangles, pairs) = self.triangles_and_pairs();
+        let all_triangles: u64 = triangles.iter().sum();
+        let all_pairs: u64 = pairs.iter().sum();
+        if all_pairs == 0 {
+            return (0.0, 0.0);
+        }
+        let coefficient = all_triangles as f64 / all_pairs as f64;
+
+        // The coefficient without each of the vertices
+        let variance: f64 = triangles
+            .iter()
+            .zip(&pairs)
+            .filter(|&(_, &p)| p < all_pairs)
+            .map(|(&t, &p)| {
+                let without = (all_triangles - t) as f64 / (all_pairs - p) as f64;
+                (coefficient - without).powi(2)
+            })
+            .sum();
+        (coefficient, variance.sqrt())
+    }
+
+    /// The core number of every vertex: the largest k such that the vertex is
+    /// in a subgraph where all vertices have at least degree k
+    ///
+    /// The degree is the total degree (in and out) in directed graphs, without
+    /// self-loops and counting parallel edges once. Uses the algorithm of
+    /// Batagelj and Zaversnik, linear in the number of edges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..4 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    /// graph.add_edge(2, 0).unwrap();
+    /// graph.add_edge(2, 3).unwrap();
+    ///
+    /// assert_eq!(graph.kcore_decomposition(), vec![2, 2, 2, 1]);
+    /// ```
+    pub fn kcore_decomposition(&self) -> Vec<u64> {
+        let n = self.num_vertices() as usize;
+        // The distinct neighbors of every vertex, in directed graphs the out
+        // and the in neighbors are distinct separately
+        let mut seen = vec![u64::MAX; n];
+        let mut stamp = 0;
+        let mut neighbors = Vec::with_capacity(n);
+        for vertex in 0..self.num_vertices() {
+            let in_neighbors = match self.directed() {
+                true => Some(self.in_neighbors(vertex)),
+                false => None,
+            };
+            let mut distinct = Vec::new();
+            for list in std::iter::once(self.out_neighbors(vertex)).chain(in_neighbors) {
+                for neighbor in list.iter() {
+                    if neighbor != vertex && seen[neighbor as usize] != stamp {
+                        seen[neighbor as usize] = stamp;
+                        distinct.push(neighbor as usize);
+                    }
+                }
+                stamp += 1;
+            }
+            neighbors.push(distinct);
+        }
+        let mut degree: Vec<usize> = neighbors.iter().map(Vec::len).collect();
+        let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+        // The vertices sorted by degree, with the start of every degree
+        let mut start = vec![0; max_degree + 2];
+        for &d in &degree {
+            start[d + 1] += 1;
+        }
+        for d in 1..start.len() {
+            start[d] += start[d - 1];
+        }
+        let mut order = vec![0; n];
+        let mut position = vec![0; n];
+        let mut next = start.clone();
+        for vertex in 0..n {
+            position[vertex] = next[degree[vertex]];
+            order[position[vertex]] = vertex;
+            next[degree[vertex]] += 1;
+        }
+
+        // Remove the vertices by increasing degree, the degree of a vertex
+        // when it is removed is its core number
+        for i in 0..n {
+            let vertex = order[i];
+            for &u in &neighbors[vertex] {
+                if degree[u] <= degree[vertex] {
+                    continue;
+                }
+                // Move the neighbor to the front of its degree and lower it
+                let d = degree[u];
+                let first = order[start[d]];
+                if first != u {
+                    order.swap(position[u], start[d]);
+                    position[first] = position[u];
+                    position[u] = start[d];
+                }
+                start[d] += 1;
+                degree[u] -= 1;
+            }
+        }
+        degree.into_iter().map(|d| d as u64).collect()
+    }
+
+    /// The number of triangles and of pairs of neighbors of every vertex
+    fn triangles_and_pairs(&self) -> (Vec<u64>, Vec<u64>) {
+        let n = self.num_vertices() as usize;
+        // The vertex whose neighbors are marked
+        let mut neighbor_of = vec![u64::MAX; n];
+        // The last neighbor (numbered over all vertices) from which a vertex
+        // was counted, to skip parallel edges
+        let mut counted = vec![u64::MAX; n];
+        let mut stamp = 0;
+        let mut neighbors = Vec::new();
+        let mut triangles = Vec::with_capacity(n);
+        let mut pairs = Vec::with_capacity(n);
+
+        for vertex in 0..self.num_vertices() {
+            neighbors.clear();
+            for neighbor in self.out_neighbors(vertex) {
+                if neighbor != vertex && neighbor_of[neighbor as usize] != vertex {
+                    neighbor_of[neighbor as usize] = vertex;
+                    neighbors.push(neighbor);
+                }
+            }
+
+            let mut closed = 0;
+            for &neighbor in &neighbors {
+                for other in self.out_neighbors(neighbor) {
+                    let o = other as usize;
+                    if neighbor_of[o] == vertex && other != neighbor && counted[o] != stamp {
+                        counted[o] = stamp;
+                        closed += 1;
+                    }
+                }
+                stamp += 1;
+            }
+
+            let k = neighbors.len() as u64;
+            let k_pairs = k * k.saturating_sub(1);
+            match self.directed() {
+                true => {
+                    triangles.push(closed);
+                    pairs.push(k_pairs);
+                }
+                // Every edge between two neighbors was seen from both ends
+                false => {
+                    triangles.push(closed / 2);
+                    pairs.push(k_pairs / 2);
+                }
+            }
+        }
+        (triangles, pairs)
+    }
+}
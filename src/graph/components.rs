@@ -0,0 +1,239 @@
+use crate::Graph;
+
+/** The component of every vertex and the size of every component
+ *
+ * Components are numbered in the order of their smallest vertex,
+ * so vertex 0 is always in component 0.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Components {
+    /// The component of every vertex
+    pub labels: Vec<u64>,
+    /// The number of vertices in every component
+    pub sizes: Vec<u64>,
+}
+
+impl Components {
+    /// Number the components in the order of their smallest vertex
+    fn from_labels(raw: &[u64], count: usize) -> Components {
+        let mut number = vec![u64::MAX; count];
+        let mut sizes = Vec::with_capacity(count);
+        let labels = raw
+            .iter()
+            .map(|&label| {
+                if number[label as usize] == u64::MAX {
+                    number[label as usize] = sizes.len() as u64;
+                    sizes.push(0);
+                }
+                sizes[number[label as usize] as usize] += 1;
+                number[label as usize]
+            })
+            .collect();
+        Components { labels, sizes }
+    }
+
+    /// The largest component, the one with the smallest vertex if there are several
+    pub fn largest(&self) -> Option<u64> {
+        let max = *self.sizes.iter().max()?;
+        self.sizes
+            .iter()
+            .position(|&size| size == max)
+            .map(|component| component as u64)
+    }
+
+    /// For every vertex whether it is in the given component
+    pub fn mask(&self, component: u64) -> Vec<bool> {
+        self.labels
+            .iter()
+            .map(|&label| label == component)
+            .collect()
+    }
+}
+
+/// Connected components.
+///
+/// In undirected graphs weak and strong components are the same.
+impl Graph {
+    /// Weakly connected components: edges connect their endpoints in both
+    /// directions
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let components = graph.weak_components();
+    /// assert_eq!(components.sizes, vec![77]);
+    /// ```
+    pub fn weak_components(&self) -> Components {
+        let n = self.num_vertices() as usize;
+        let mut labels = vec![u64::MAX; n];
+        let mut count = 0;
+        let mut stack = Vec::new();
+        for root in 0..n {
+            if labels[root] != u64::MAX {
+                continue;
+            }
+            labels[root] = count;
+            stack.push(root as u64);
+            while let Some(vertex) = stack.pop() {
+                for neighbor in self.all_neighbors(vertex) {
+                    if labels[neighbor as usize] == u64::MAX {
+                        labels[neighbor as usize] = count;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            count += 1;
+        }
+        Components::from_labels(&labels, count as usize)
+    }
+
+    /// Strongly connected components: two vertices are in the same component
+    /// if there are directed paths from each to the other
+    ///
+    /// Uses Tarjan's algorithm with its own stack, so long paths can't overflow
+    /// the (small) wasm stack.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::{Graph, GraphFile};
+    ///
+    /// let mut file = GraphFile::default();
+    /// file.directed = true;
+    /// let mut graph = Graph::from(file);
+    /// for _ in 0..3 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 0).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    ///
+    /// let components = graph.strong_components();
+    /// assert_eq!(components.labels, vec![0, 0, 1]);
+    /// assert_eq!(components.sizes, vec![2, 1]);
+    /// ```
+    pub fn strong_components(&self) -> Components {
+        const UNVISITED: u64 = u64::MAX;
+        let n = self.num_vertices() as usize;
+        // Discovery time and the smallest discovery time reachable over the
+        // search tree and one more edge
+        let mut index = vec![UNVISITED; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        // Visited vertices without a component yet
+        let mut stack = Vec::new();
+        // The search path with the position of the next out neighbor
+        let mut path: Vec<(u64, usize)> = Vec::new();
+        let mut labels = vec![0; n];
+        let mut time = 0;
+        let mut count = 0;
+
+        for root in 0..self.num_vertices() {
+            if index[root as usize] != UNVISITED {
+                continue;
+            }
+            path.push((root, 0));
+            index[root as usize] = time;
+            low[root as usize] = time;
+            time += 1;
+            stack.push(root);
+            on_stack[root as usize] = true;
+
+            while let Some((vertex, next)) = path.last_mut() {
+                let vertex = *vertex;
+                let neighbors = self.out_neighbors(vertex);
+                if let Some(neighbor) = neighbors.get(*next) {
+                    *next += 1;
+                    let w = neighbor as usize;
+                    if index[w] == UNVISITED {
+                        index[w] = time;
+                        low[w] = time;
+                        time += 1;
+                        stack.push(neighbor);
+                        on_stack[w] = true;
+                        path.push((neighbor, 0));
+                    } else if on_stack[w] {
+                        low[vertex as usize] = low[vertex as usize].min(index[w]);
+                    }
+                    continue;
+                }
+
+                path.pop();
+                if let Some(&(parent, _)) = path.last() {
+                    low[parent as usize] = low[parent as usize].min(low[vertex as usize]);
+                }
+                if low[vertex as usize] == index[vertex as usize] {
+                    // The vertex is the root of a component
+                    while let Some(member) = stack.pop() {
+                        on_stack[member as usize] = false;
+                        labels[member as usize] = count;
+                        if member == vertex {
+                            break;
+                        }
+                    }
+                    count += 1;
+                }
+            }
+        }
+        Components::from_labels(&labels, count as usize)
+    }
+
+    /// The components as in graph-tool's `label_components`: strong components
+    /// in directed graphs, weak (connected) components in undirected graphs
+    pub fn components(&self) -> Components {
+        match self.directed() {
+            true => self.strong_components(),
+            false => self.weak_components(),
+        }
+    }
+
+    /// For every vertex whether it is in the largest component (see
+    /// `Graph::components`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..4 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(1, 2).unwrap();
+    /// graph.add_edge(2, 3).unwrap();
+    ///
+    /// assert_eq!(graph.largest_component(), vec![false, true, true, true]);
+    /// ```
+    pub fn largest_component(&self) -> Vec<bool> {
+        let components = self.components();
+        match components.largest() {
+            Some(largest) => components.mask(largest),
+            None => Vec::new(),
+        }
+    }
+
+    /// A copy of the graph with only the vertices for which `keep` is true
+    /// and the edges between them
+    ///
+    /// All properties are carried over. The remaining vertices are renumbered
+    /// in their order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keep` does not have one entry per vertex
+    pub fn induced_subgraph(&self, keep: &[bool]) -> Result<Graph, String> {
+        let mut subgraph = self.clone();
+        subgraph.retain_vertices(keep)?;
+        Ok(subgraph)
+    }
+
+    /// The largest component as a graph of its own, with all properties
+    /// (see `Graph::largest_component`)
+    pub fn extract_largest_component(&self) -> Graph {
+        self.induced_subgraph(&self.largest_component()).unwrap()
+    }
+}
@@ -0,0 +1,216 @@
+use std::cell::OnceCell;
+
+use crate::graph_file::properties::{Property, PropertyMapType};
+use crate::Graph;
+
+/// Adding and removing vertices and edges.
+///
+/// Vertex and edge properties are kept in sync: new vertices and edges
+/// get the default value (zero, empty or `None`), the values of removed
+/// ones are deleted.
+///
+/// Vertex and edge indices are positions, as in graph-tool: removing a
+/// vertex moves all later vertices down by one, removing an edge moves all
+/// later edges down by one. A new edge gets the next index, so the indices
+/// of the other edges don't change. In the file edges are ordered by their
+/// source, so a graph written and read back numbers its edges in that order.
+impl Graph {
+    /// Add a vertex without edges and return its index
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert_eq!(graph.add_vertex(), 0);
+    /// assert_eq!(graph.add_vertex(), 1);
+    /// assert_eq!(graph.num_vertices(), 2);
+    /// ```
+    pub fn add_vertex(&mut self) -> u64 {
+        let vertex = self.file.num_vertices;
+        self.file.out_neighbors.add_vertex();
+        self.file.num_vertices += 1;
+        for property in self.properties_of(PropertyMapType::Vertex) {
+            property.insert_default(vertex as usize);
+        }
+        self.reset_index();
+        vertex
+    }
+
+    /// Add an edge from `source` to `target` and return its index
+    ///
+    /// The new edge has the index `num_edges()` (its values are appended to
+    /// the edge properties), the indices of all other edges stay the same.
+    /// The edge is stored after the other out edges of `source`, which moves
+    /// the neighbors of all later vertices (linear in the number of edges).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the vertices does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_vertex();
+    /// graph.add_vertex();
+    /// assert_eq!(graph.add_edge(1, 0).unwrap(), 0);
+    /// assert_eq!(graph.add_edge(0, 1).unwrap(), 1);
+    /// assert_eq!(graph.edge(0), Some((1, 0)));
+    /// assert_eq!(graph.edge(1), Some((0, 1)));
+    /// assert!(graph.add_edge(0, 2).is_err());
+    /// ```
+    pub fn add_edge(&mut self, source: u64, target: u64) -> Result<u64, String> {
+        self.check_vertex(source)?;
+        self.check_vertex(target)?;
+        self.load_properties(PropertyMapType::Edge)?;
+
+        let edge = self.file.out_neighbors.insert_edge(source, target);
+        self.file.num_edges += 1;
+        for property in self.properties_of(PropertyMapType::Edge) {
+            property.insert_default(edge as usize);
+        }
+        self.reset_index();
+        Ok(edge)
+    }
+
+    /// Remove a vertex and all its edges
+    ///
+    /// All vertices with a higher index move down by one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vertex does not exist
+    pub fn remove_vertex(&mut self, vertex: u64) -> Result<(), String> {
+        self.check_vertex(vertex)?;
+        self.load_properties(PropertyMapType::Vertex)?;
+        self.load_properties(PropertyMapType::Edge)?;
+
+        let kept = self.file.out_neighbors.remove_vertex(vertex);
+        self.file.num_vertices -= 1;
+        self.file.num_edges = self.file.out_neighbors.num_edges();
+        for property in self.properties_of(PropertyMapType::Vertex) {
+            property.retain_rows(|row| row != vertex as usize);
+        }
+        for property in self.properties_of(PropertyMapType::Edge) {
+            property.retain_rows(|row| kept[row]);
+        }
+        self.reset_index();
+        Ok(())
+    }
+
+    /// Keep only the vertices for which `keep` is true, with the edges
+    /// between them
+    ///
+    /// The remaining vertices are renumbered in their order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keep` does not have one entry per vertex
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..3 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    ///
+    /// graph.retain_vertices(&[false, true, true]).unwrap();
+    /// assert_eq!(graph.num_vertices(), 2);
+    /// assert_eq!(graph.edges(), (vec![0], vec![1]));
+    /// ```
+    pub fn retain_vertices(&mut self, keep: &[bool]) -> Result<(), String> {
+        if keep.len() as u64 != self.file.num_vertices {
+            return Err(format!(
+                "Expected {} entries in the vertex mask, got {}",
+                self.file.num_vertices,
+                keep.len()
+            ));
+        }
+        self.load_properties(PropertyMapType::Vertex)?;
+        self.load_properties(PropertyMapType::Edge)?;
+
+        let kept = self.file.out_neighbors.retain_vertices(keep);
+        self.file.num_vertices = self.file.out_neighbors.num_vertices();
+        self.file.num_edges = self.file.out_neighbors.num_edges();
+        for property in self.properties_of(PropertyMapType::Vertex) {
+            property.retain_rows(|row| keep[row]);
+        }
+        for property in self.properties_of(PropertyMapType::Edge) {
+            property.retain_rows(|row| kept[row]);
+        }
+        self.reset_index();
+        Ok(())
+    }
+
+    /// Remove the edge with the given index
+    ///
+    /// All edges with a higher index move down by one. This is linear in the
+    /// number of edges.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the edge does not exist
+    pub fn remove_edge(&mut self, edge: u64) -> Result<(), String> {
+        if edge >= self.file.num_edges {
+            return Err(format!("Edge {} does not exist", edge));
+        }
+        self.load_properties(PropertyMapType::Edge)?;
+
+        self.file.out_neighbors.remove_edge(edge);
+        self.file.num_edges -= 1;
+        for property in self.properties_of(PropertyMapType::Edge) {
+            property.retain_rows(|row| row != edge as usize);
+        }
+        self.reset_index();
+        Ok(())
+    }
+
+    /// Remove all edges, the vertices are kept
+    pub fn clear_edges(&mut self) {
+        self.file.out_neighbors.clear_edges();
+        self.file.num_edges = 0;
+        for property in self.properties_of(PropertyMapType::Edge) {
+            property.retain_rows(|_| false);
+        }
+        self.reset_index();
+    }
+
+    pub(crate) fn check_vertex(&self, vertex: u64) -> Result<(), String> {
+        if vertex >= self.file.num_vertices {
+            return Err(format!("Vertex {} does not exist", vertex));
+        }
+        Ok(())
+    }
+
+    /// Decode the properties of the given map type before they are changed,
+    /// such that invalid values are an error and the graph stays unchanged
+    fn load_properties(&self, map_type: PropertyMapType) -> Result<(), String> {
+        self.file
+            .properties
+            .iter()
+            .filter(|property| property.map_type == map_type)
+            .try_for_each(Property::load)
+            .map_err(|error| error.to_string())
+    }
+
+    fn properties_of(&mut self, map_type: PropertyMapType) -> impl Iterator<Item = &mut Property> {
+        self.file
+            .properties
+            .iter_mut()
+            .filter(move |property| property.map_type == map_type)
+    }
+
+    /// Drop the neighbor index, it is rebuilt on the next query
+    fn reset_index(&mut self) {
+        self.incidence = OnceCell::new();
+    }
+}
@@ -1,24 +1,35 @@
-use crate::{Graph, GraphFile};
+use std::cell::OnceCell;
+use std::io::Read;
+
+use crate::graph_file::is_gt_file;
+use crate::graph_file::schema::{GraphHeader, GraphSchema};
+use crate::{Graph, GraphFile, GtError, LoadOptions};
 
 /// Implements the `TryFrom` trait for `Graph` from a `Vec<u8>`.
 ///
 /// This conversion allows creating a `Graph` from a byte vector, decoding the data
 /// and constructing a `Graph` object from it.
 ///
+/// The file is decompressed and parsed in one pass and all properties are
+/// decoded, nothing but the graph is kept. Properties are not decoded lazily
+/// by default, because that keeps the whole decompressed file in memory for
+/// the lifetime of the graph. See `Graph::from_bytes_with` and
+/// `LoadOptions::lazy` to decode the properties only when they are first
+/// accessed through `Graph::property`.
+///
 /// # Arguments
 ///
 /// * `data` - The byte vector containing the encoded graph data.
 ///
 /// # Errors
 ///
-/// Returns an error if the data could not be decoded or the graph could not be created.
+/// Returns a `GtError` if the data could not be decoded or the graph could not be created.
 ///
 /// # Examples
 ///
 /// ```
 /// use std::convert::TryFrom;
-/// use wasm_bindgen::JsValue;
-/// use crate::graph::Graph;
+/// use gt_graph_wasm::Graph;
 ///
 /// let data: Vec<u8> = vec![/* encoded graph data */];
 /// let graph_result = Graph::try_from(data);
@@ -36,18 +47,193 @@ use crate::{Graph, GraphFile};
 /// }
 /// ```
 impl TryFrom<Vec<u8>> for Graph {
-    type Error = String;
+    type Error = GtError;
 
     fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
-        let data = crate::decode::decodebuffer(&data)?;
-        let graph_file: GraphFile = data.as_slice().try_into()?;
-        Ok(Graph { file: graph_file })
+        Graph::from_bytes_with(data, &LoadOptions::default())
+    }
+}
+
+impl Graph {
+    /// Same as `Graph::try_from`, loading only what the options ask for
+    ///
+    /// With `options.lazy` the decompressed file is kept and the properties
+    /// are decoded when they are first accessed, see `GraphFile::from_buffer`.
+    /// This is faster if only some properties are used, but the decompressed
+    /// file stays in memory for the lifetime of the graph, so it is off by
+    /// default. Otherwise the file is parsed as by `Graph::from_reader`, all
+    /// properties are decoded while loading and only the graph is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The (compressed) gt file
+    /// * `options` - e.g. the names of the properties to load, the other
+    ///   properties are skipped and never allocated
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the data could not be decoded or the graph could not be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::{Graph, LoadOptions};
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let options = LoadOptions::default().with_properties(["_pos", "label"]);
+    /// let graph = Graph::from_bytes_with(data.clone(), &options).unwrap();
+    /// assert_eq!(graph.property_names(None), vec!["label", "_pos"]);
+    ///
+    /// let graph = Graph::from_bytes_with(data, &options.with_lazy(true)).unwrap();
+    /// assert!(graph.property_info().iter().all(|info| !info.loaded));
+    /// ```
+    pub fn from_bytes_with(data: Vec<u8>, options: &LoadOptions) -> Result<Graph, GtError> {
+        // An uncompressed file is kept as it is
+        if options.lazy && is_gt_file(&data) {
+            return Ok(Graph::from(GraphFile::from_buffer_with(data, options)?));
+        }
+        Graph::from_reader_with(data.as_slice(), options)
+    }
+
+    /// Read the header of a (compressed) gt file
+    ///
+    /// Only the first bytes are decompressed, see `GraphFile::read_header`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the data could not be decoded or the header is invalid.
+    pub fn read_header(data: &[u8]) -> Result<GraphHeader, GtError> {
+        if is_gt_file(data) {
+            return GraphFile::read_header(data);
+        }
+        Graph::read_header_from_reader(data)
+    }
+
+    /// Same as [`Graph::read_header`] for any reader
+    ///
+    /// Compressed input is decompressed only up to the end of the header.
+    pub fn read_header_from_reader<R: Read>(reader: R) -> Result<GraphHeader, GtError> {
+        let reader = crate::decode::decodestream(reader).map_err(GtError::Decode)?;
+        GraphFile::read_header_from_reader(reader)
+    }
+
+    /// Read the header, the number of edges and the property schema of a
+    /// (compressed) gt file without decoding the data, see `GraphFile::read_schema`
+    ///
+    /// Uncompressed files are skipped through without reading the data,
+    /// compressed files are decompressed on the fly but nothing is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the data could not be decoded or the file is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst");
+    /// let schema = Graph::read_schema(data).unwrap();
+    /// assert_eq!(schema.header.num_vertices, 77);
+    /// assert_eq!(schema.num_edges, 254);
+    /// assert_eq!(schema.properties.len(), 8);
+    /// ```
+    pub fn read_schema(data: &[u8]) -> Result<GraphSchema, GtError> {
+        if is_gt_file(data) {
+            return GraphFile::read_schema(data);
+        }
+        Graph::read_schema_from_reader(data)
+    }
+
+    /// Same as [`Graph::read_schema`] for any reader
+    ///
+    /// The input is read (and decompressed) only up to the end of the
+    /// property schema, the skipped data is read and dropped.
+    pub fn read_schema_from_reader<R: Read>(reader: R) -> Result<GraphSchema, GtError> {
+        let reader = crate::decode::decodestream(reader).map_err(GtError::Decode)?;
+        GraphFile::read_schema_from_reader(reader)
+    }
+
+    /// Load a graph from any reader.
+    ///
+    /// The compression is detected from the first bytes. zstd and gzip
+    /// compressed input is decompressed and parsed in one pass, without
+    /// holding the decompressed file in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the (compressed) gt file, e.g. a `std::fs::File`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the data could not be decoded or the graph could not be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst");
+    /// let graph = Graph::from_reader(&data[..]).unwrap();
+    /// assert_eq!(graph.num_vertices(), 77);
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Graph, GtError> {
+        Graph::from_reader_with(reader, &LoadOptions::default())
+    }
+
+    /// Same as [`Graph::from_reader`], loading only what the options ask for
+    ///
+    /// With `options.lazy` the decompressed file is read into a buffer which
+    /// is kept, see `Graph::from_bytes_with`.
+    pub fn from_reader_with<R: Read>(reader: R, options: &LoadOptions) -> Result<Graph, GtError> {
+        let mut reader = crate::decode::decodestream(reader).map_err(GtError::Decode)?;
+        if options.lazy {
+            let mut data = Vec::new();
+            reader
+                .read_to_end(&mut data)
+                .map_err(|e| GtError::Decode(e.to_string()))?;
+            return Ok(Graph::from(GraphFile::from_buffer_with(data, options)?));
+        }
+        let graph_file = GraphFile::from_reader_with(&mut reader, options)?;
+        // Read to the end such that the checksums of the compressed data are verified
+        std::io::copy(&mut reader, &mut std::io::sink())
+            .map_err(|e| GtError::Decode(e.to_string()))?;
+        Ok(Graph::from(graph_file))
+    }
+
+    /// Serialize the graph into the (uncompressed) gt binary format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let bytes = graph.to_bytes();
+    /// let copy = Graph::try_from(bytes).unwrap();
+    /// assert_eq!(copy.num_edges(), graph.num_edges());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.file.to_bytes()
+    }
+
+    /// Serialize the graph into the gt binary format and compress it with zstd.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The zstd compression level (0 selects the default level)
+    pub fn to_bytes_zstd(&self, level: i32) -> Result<Vec<u8>, String> {
+        crate::encode::encodebuffer_zstd(&self.file.to_bytes(), level)
     }
 }
 
 /// Implements the `From` trait for `Graph` from a `GraphFile`.
 impl From<GraphFile> for Graph {
     fn from(file: GraphFile) -> Self {
-        Graph { file }
+        Graph {
+            file,
+            incidence: OnceCell::new(),
+        }
     }
 }
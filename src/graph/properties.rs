@@ -1,5 +1,5 @@
 use crate::{
-    graph_file::properties::{Property, PropertyMapType},
+    graph_file::properties::{Element, Property, PropertyInfo, PropertyMapType, PropertyType},
     Graph,
 };
 
@@ -14,31 +14,68 @@ impl Graph {
     ///
     /// # Errors
     ///
-    /// Returns an error if the property does not exist
+    /// Returns an error if the property does not exist or its values can not
+    /// be decoded (only for lazily loaded graphs, see `Property::load`)
     ///
     /// # Example
     ///
     /// ```
-    /// use gt_graph_wasm::graph::Graph;
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::PropertyMapType;
     ///
-    /// let graph = Graph::new();
-    /// let property = graph.property("name".to_string(), PropertyMapType::Graph);
+    /// let mut graph = Graph::new();
+    /// let property = graph.property("name".to_string(), Some(PropertyMapType::Graph));
     /// ```
     pub fn property(
         &mut self,
         name: String,
         property_type: Option<PropertyMapType>,
     ) -> Result<&mut Property, String> {
-        let property_type = property_type.as_ref();
-        for property in &mut self.file.properties {
-            if property.name == name
-                && (property_type.as_ref().is_none()
-                    || property.map_type == *property_type.unwrap())
-            {
-                return Ok(property);
-            }
-        }
-        Err(format!("Property '{}' not found", name))
+        let index = self.position(&name, property_type)?;
+        let property = &mut self.file.properties[index];
+        property.load().map_err(|error| error.to_string())?;
+        Ok(property)
+    }
+
+    /// Same as [`Graph::property`], for read-only access
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::PropertyMapType;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    ///
+    /// let label = graph.property_ref("label", Some(PropertyMapType::Vertex)).unwrap();
+    /// assert_eq!(label.as_strings().unwrap()[0], "Myriel");
+    /// ```
+    pub fn property_ref(
+        &self,
+        name: &str,
+        property_type: Option<PropertyMapType>,
+    ) -> Result<&Property, String> {
+        let index = self.position(name, property_type)?;
+        let property = &self.file.properties[index];
+        property.load().map_err(|error| error.to_string())?;
+        Ok(property)
+    }
+
+    /// Index of the first property with the given name (and map type)
+    fn position(
+        &self,
+        name: &str,
+        property_type: Option<PropertyMapType>,
+    ) -> Result<usize, String> {
+        self.file
+            .properties
+            .iter()
+            .position(|property| {
+                property.name == name
+                    && property_type.is_none_or(|map_type| property.map_type == map_type)
+            })
+            .ok_or_else(|| format!("Property '{}' not found", name))
     }
 
     pub fn graph_property(&mut self, name: String) -> Result<&mut Property, String> {
@@ -62,7 +99,7 @@ impl Graph {
     /// # Example
     ///
     /// ```
-    /// use gt_graph_wasm::graph::Graph;
+    /// use gt_graph_wasm::Graph;
     /// use gt_graph_wasm::graph_file::properties::PropertyMapType;
     ///
     /// let graph = Graph::new();
@@ -95,4 +132,209 @@ impl Graph {
     pub fn edge_property_names(&self) -> Vec<String> {
         self.property_names(Some(PropertyMapType::Edge))
     }
+
+    /// Name, map type, value type, length and approximate size of every property
+    ///
+    /// Unlike [`Graph::property_names`] the entries can be told apart
+    /// if the same name is used for different map types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::{PropertyMapType, PropertyType};
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    ///
+    /// let info = graph.property_info();
+    /// let value = info.iter().find(|info| info.name == "value").unwrap();
+    /// assert_eq!(value.map_type, PropertyMapType::Edge);
+    /// assert_eq!(value.value_type, PropertyType::Int16);
+    /// assert_eq!(value.len, 254);
+    /// assert_eq!(value.bytes, 254 * 2);
+    /// ```
+    pub fn property_info(&self) -> Vec<PropertyInfo> {
+        self.file.properties.iter().map(Property::info).collect()
+    }
+
+    /// Create a property with default values (zero, empty or `None`)
+    ///
+    /// A property with the same name and map type is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the property
+    /// * `map_type` - Whether it is a graph, vertex or edge property
+    /// * `value_type` - The type of the values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::PropertyType;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let mut graph = Graph::try_from(data).unwrap();
+    ///
+    /// let color = graph.new_vertex_property("color", PropertyType::VectorDouble);
+    /// assert_eq!(color.len(), 77);
+    /// color.set(vec![vec![1.0, 0.0, 0.0]; 77]).unwrap();
+    /// ```
+    pub fn new_property(
+        &mut self,
+        name: &str,
+        map_type: PropertyMapType,
+        value_type: PropertyType,
+    ) -> &mut Property {
+        let len = self.property_len(map_type);
+        self.insert_property(Property::new(name, map_type, value_type, len))
+    }
+
+    pub fn new_graph_property(&mut self, name: &str, value_type: PropertyType) -> &mut Property {
+        self.new_property(name, PropertyMapType::Graph, value_type)
+    }
+    pub fn new_vertex_property(&mut self, name: &str, value_type: PropertyType) -> &mut Property {
+        self.new_property(name, PropertyMapType::Vertex, value_type)
+    }
+    pub fn new_edge_property(&mut self, name: &str, value_type: PropertyType) -> &mut Property {
+        self.new_property(name, PropertyMapType::Edge, value_type)
+    }
+
+    /// Replace all values of an existing property
+    ///
+    /// There has to be one value per vertex (edge) and the values have
+    /// to fit the value type of the property (see [`Element`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the property does not exist or if the number
+    /// or the type of the values does not match
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::PropertyType;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_vertex();
+    /// graph.add_vertex();
+    /// graph.new_vertex_property("score", PropertyType::Double);
+    ///
+    /// assert!(graph.set_vertex_property("score", vec![0.5, 1.5]).is_ok());
+    /// assert!(graph.set_vertex_property("score", vec![0.5]).is_err());
+    /// assert!(graph.set_vertex_property("score", vec![1i32, 2i32]).is_err());
+    /// ```
+    pub fn set_property<T: Element>(
+        &mut self,
+        name: &str,
+        map_type: PropertyMapType,
+        values: Vec<T>,
+    ) -> Result<(), String> {
+        let len = self.property_len(map_type);
+        if values.len() != len {
+            return Err(format!(
+                "Expected {} values for {:?} property '{}', got {}",
+                len,
+                map_type,
+                name,
+                values.len()
+            ));
+        }
+        self.property(name.to_string(), Some(map_type))?.set(values)
+    }
+
+    pub fn set_graph_property<T: Element>(&mut self, name: &str, value: T) -> Result<(), String> {
+        self.set_property(name, PropertyMapType::Graph, vec![value])
+    }
+    pub fn set_vertex_property<T: Element>(
+        &mut self,
+        name: &str,
+        values: Vec<T>,
+    ) -> Result<(), String> {
+        self.set_property(name, PropertyMapType::Vertex, values)
+    }
+    pub fn set_edge_property<T: Element>(
+        &mut self,
+        name: &str,
+        values: Vec<T>,
+    ) -> Result<(), String> {
+        self.set_property(name, PropertyMapType::Edge, values)
+    }
+
+    /// Delete a property and return it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the property does not exist
+    pub fn remove_property(
+        &mut self,
+        name: &str,
+        map_type: PropertyMapType,
+    ) -> Result<Property, String> {
+        let index = self.position(name, Some(map_type))?;
+        Ok(self.file.properties.remove(index))
+    }
+
+    pub fn remove_graph_property(&mut self, name: &str) -> Result<Property, String> {
+        self.remove_property(name, PropertyMapType::Graph)
+    }
+    pub fn remove_vertex_property(&mut self, name: &str) -> Result<Property, String> {
+        self.remove_property(name, PropertyMapType::Vertex)
+    }
+    pub fn remove_edge_property(&mut self, name: &str) -> Result<Property, String> {
+        self.remove_property(name, PropertyMapType::Edge)
+    }
+
+    /// Number of values of a property of the given map type
+    pub(crate) fn property_len(&self, map_type: PropertyMapType) -> usize {
+        match map_type {
+            PropertyMapType::Graph => 1,
+            PropertyMapType::Vertex => self.file.num_vertices as usize,
+            PropertyMapType::Edge => self.file.num_edges as usize,
+        }
+    }
+
+    /// The values of a numeric property (int16_t, int32_t, int64_t or double)
+    /// as f64, e.g. for weights
+    pub(crate) fn numeric_values(
+        &self,
+        name: &str,
+        map_type: PropertyMapType,
+    ) -> Result<Vec<f64>, String> {
+        let property = self.property_ref(name, Some(map_type))?;
+        let values = match property.value_type() {
+            PropertyType::Int16 => property.get::<i16>()?.iter().map(|v| *v as f64).collect(),
+            PropertyType::Int32 => property.get::<i32>()?.iter().map(|v| *v as f64).collect(),
+            PropertyType::Int64 => property.get::<i64>()?.iter().map(|v| *v as f64).collect(),
+            PropertyType::Double | PropertyType::LongDouble => property.get::<f64>()?.to_vec(),
+            value_type => {
+                return Err(format!(
+                    "Property '{}' has values of type {}, expected numbers",
+                    name,
+                    value_type.name()
+                ))
+            }
+        };
+        Ok(values)
+    }
+
+    /// Add a property, replacing the one with the same name and map type
+    pub(crate) fn insert_property(&mut self, property: Property) -> &mut Property {
+        let properties = &mut self.file.properties;
+        let index = properties
+            .iter()
+            .position(|p| p.name == property.name && p.map_type == property.map_type);
+        match index {
+            Some(index) => {
+                properties[index] = property;
+                &mut properties[index]
+            }
+            None => {
+                properties.push(property);
+                properties.last_mut().unwrap()
+            }
+        }
+    }
 }
@@ -0,0 +1,348 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::graph_file::properties::PropertyMapType;
+use crate::Graph;
+
+/** Distances and predecessors of all vertices from a source
+ *
+ * As in graph-tool, vertices which were not reached have the
+ * distance `f64::INFINITY` and are their own predecessor (as is
+ * the source).
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths {
+    pub distances: Vec<f64>,
+    /// The previous vertex on a shortest path from the source
+    pub predecessors: Vec<u64>,
+}
+
+/// A path between two vertices, see `Graph::shortest_path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    /// The vertices from the source to the target
+    pub vertices: Vec<u64>,
+    /// The edges between the vertices (one less than the vertices)
+    pub edges: Vec<u64>,
+    /// The sum of the weights of the edges
+    pub distance: f64,
+}
+
+/// Options for `Graph::shortest_path` and `Graph::shortest_distances`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathOptions {
+    /// Name of the edge property with the weights (int16_t, int32_t,
+    /// int64_t or double), every edge has the weight 1 if `None`
+    pub weight: Option<String>,
+    /// Use Bellman-Ford instead of Dijkstra, which allows negative weights
+    pub negative_weights: bool,
+    /// Name of a vertex property with positions (vector<double>). If it is
+    /// set, paths are searched with A*, guided by the euclidean distance
+    /// to the target.
+    pub position: Option<String>,
+}
+
+/// Shortest paths from a single source.
+///
+/// The paths follow the out edges, in undirected graphs all edges.
+/// Weights are read from an edge property as f64 (int64 weights beyond
+/// 2^53 are rounded).
+impl Graph {
+    /// Shortest distances from `source` with Dijkstra's algorithm
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The vertex to start from
+    /// * `weight` - Name of the edge property with the weights, 1 for every edge if `None`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source or the weight property does not exist,
+    /// the property is not numeric or a weight is negative (or NaN)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let paths = graph.dijkstra(0, Some("value")).unwrap();
+    /// assert_eq!(paths.distances[0], 0.0);
+    /// assert!(paths.distances.iter().all(|d| d.is_finite()));
+    /// ```
+    pub fn dijkstra(&self, source: u64, weight: Option<&str>) -> Result<ShortestPaths, String> {
+        self.check_vertex(source)?;
+        let weights = self.edge_weights(weight)?;
+        if let Some(edge) = weights.iter().position(|w| *w < 0.0) {
+            return Err(format!(
+                "Edge {} has the negative weight {}, use Bellman-Ford instead",
+                edge, weights[edge]
+            ));
+        }
+        Ok(self.best_first(source, None, &weights, |_| 0.0))
+    }
+
+    /// Shortest distances from `source` with the Bellman-Ford algorithm,
+    /// which allows negative weights
+    ///
+    /// In undirected graphs an edge with a negative weight is a negative cycle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source or the weight property does not exist,
+    /// the property is not numeric or a negative cycle can be reached from
+    /// the source
+    pub fn bellman_ford(&self, source: u64, weight: Option<&str>) -> Result<ShortestPaths, String> {
+        self.check_vertex(source)?;
+        let weights = self.edge_weights(weight)?;
+        let mut paths = ShortestPaths::new(self.num_vertices());
+        paths.distances[source as usize] = 0.0;
+
+        // Without negative cycles every shortest path has at most n - 1 edges,
+        // so the distances don't change anymore after n - 1 rounds
+        for _ in 0..self.num_vertices() {
+            let mut changed = false;
+            for vertex in 0..self.num_vertices() {
+                let distance = paths.distances[vertex as usize];
+                if distance == f64::INFINITY {
+                    continue;
+                }
+                for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                    let candidate = distance + weights[edge as usize];
+                    if candidate < paths.distances[target as usize] {
+                        paths.distances[target as usize] = candidate;
+                        paths.predecessors[target as usize] = vertex;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return Ok(paths);
+            }
+        }
+        Err(format!(
+            "A negative cycle can be reached from vertex {}",
+            source
+        ))
+    }
+
+    /// Shortest path from `source` to `target` with A*
+    ///
+    /// The search is guided by the euclidean distance to the target, computed
+    /// from the vertex property `position` (vector<double>). The result is only
+    /// a shortest path if no edge is shorter than the distance between its
+    /// endpoints (e.g. if the weights are the lengths of the edges).
+    ///
+    /// The search stops at the target: the distances of vertices which were not
+    /// settled before are upper bounds (or infinite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a vertex or property does not exist, the properties
+    /// have the wrong type or a weight is negative (or NaN)
+    pub fn astar(
+        &self,
+        source: u64,
+        target: u64,
+        weight: Option<&str>,
+        position: &str,
+    ) -> Result<ShortestPaths, String> {
+        self.check_vertex(source)?;
+        self.check_vertex(target)?;
+        let weights = self.edge_weights(weight)?;
+        if let Some(edge) = weights.iter().position(|w| *w < 0.0) {
+            return Err(format!(
+                "Edge {} has the negative weight {}",
+                edge, weights[edge]
+            ));
+        }
+        let positions = self
+            .property_ref(position, Some(PropertyMapType::Vertex))?
+            .as_vec_f64()
+            .ok_or_else(|| format!("Property '{}' is not a vector<double> property", position))?;
+        let goal = &positions[target as usize];
+        let heuristic = |vertex: u64| -> f64 {
+            let position = &positions[vertex as usize];
+            position
+                .iter()
+                .zip(goal)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt()
+        };
+        Ok(self.best_first(source, Some(target), &weights, heuristic))
+    }
+
+    /// Shortest distances from `source` to all vertices
+    ///
+    /// Uses Dijkstra, or Bellman-Ford if `options.negative_weights` is set.
+    /// The position is ignored.
+    ///
+    /// # Errors
+    ///
+    /// See `Graph::dijkstra` and `Graph::bellman_ford`
+    pub fn shortest_distances(
+        &self,
+        source: u64,
+        options: &PathOptions,
+    ) -> Result<ShortestPaths, String> {
+        let weight = options.weight.as_deref();
+        match options.negative_weights {
+            true => self.bellman_ford(source, weight),
+            false => self.dijkstra(source, weight),
+        }
+    }
+
+    /// Shortest path from `source` to `target`
+    ///
+    /// Uses A* if `options.position` is set, otherwise Dijkstra or Bellman-Ford
+    /// (see `Graph::shortest_distances`). Returns `None` if the target can't be
+    /// reached. If there are several edges between two vertices of the path,
+    /// the one with the smallest weight is used.
+    ///
+    /// # Errors
+    ///
+    /// See `Graph::dijkstra`, `Graph::bellman_ford` and `Graph::astar`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::shortest_paths::PathOptions;
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..3 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    ///
+    /// let path = graph.shortest_path(0, 2, &PathOptions::default()).unwrap().unwrap();
+    /// assert_eq!(path.vertices, vec![0, 1, 2]);
+    /// assert_eq!(path.edges, vec![0, 1]);
+    /// assert_eq!(path.distance, 2.0);
+    /// ```
+    pub fn shortest_path(
+        &self,
+        source: u64,
+        target: u64,
+        options: &PathOptions,
+    ) -> Result<Option<Path>, String> {
+        self.check_vertex(target)?;
+        let weight = options.weight.as_deref();
+        let paths = match &options.position {
+            Some(position) => self.astar(source, target, weight, position)?,
+            None => self.shortest_distances(source, options)?,
+        };
+        let distance = paths.distances[target as usize];
+        if distance == f64::INFINITY {
+            return Ok(None);
+        }
+
+        let weights = self.edge_weights(weight)?;
+        let mut vertices = vec![target];
+        let mut edges = Vec::new();
+        let mut vertex = target;
+        while vertex != source {
+            let predecessor = paths.predecessors[vertex as usize];
+            // The lightest of the (parallel) edges from the predecessor
+            let edge = self
+                .out_edges(predecessor)
+                .zip(self.out_neighbors(predecessor))
+                .filter(|&(_, neighbor)| neighbor == vertex)
+                .map(|(edge, _)| edge)
+                .min_by(|a, b| weights[*a as usize].total_cmp(&weights[*b as usize]))
+                .unwrap();
+            edges.push(edge);
+            vertices.push(predecessor);
+            vertex = predecessor;
+        }
+        vertices.reverse();
+        edges.reverse();
+        Ok(Some(Path {
+            vertices,
+            edges,
+            distance,
+        }))
+    }
+
+    /// The weight of every edge from an edge property, 1 if there is no property
+    pub(crate) fn edge_weights(&self, weight: Option<&str>) -> Result<Vec<f64>, String> {
+        let Some(name) = weight else {
+            return Ok(vec![1.0; self.num_edges() as usize]);
+        };
+        let weights = self.numeric_values(name, PropertyMapType::Edge)?;
+        if let Some(edge) = weights.iter().position(|w| w.is_nan()) {
+            return Err(format!("Edge {} has the weight NaN", edge));
+        }
+        Ok(weights)
+    }
+
+    /// Dijkstra (without heuristic) or A*, stopping at the target if there is one
+    pub(crate) fn best_first(
+        &self,
+        source: u64,
+        target: Option<u64>,
+        weights: &[f64],
+        heuristic: impl Fn(u64) -> f64,
+    ) -> ShortestPaths {
+        let mut paths = ShortestPaths::new(self.num_vertices());
+        let mut settled = vec![false; self.num_vertices() as usize];
+        let mut queue = BinaryHeap::new();
+        paths.distances[source as usize] = 0.0;
+        queue.push(Candidate(heuristic(source), source));
+
+        while let Some(Candidate(_, vertex)) = queue.pop() {
+            if settled[vertex as usize] {
+                continue;
+            }
+            settled[vertex as usize] = true;
+            if Some(vertex) == target {
+                break;
+            }
+            let distance = paths.distances[vertex as usize];
+            for (edge, neighbor) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                let candidate = distance + weights[edge as usize];
+                if candidate < paths.distances[neighbor as usize] {
+                    paths.distances[neighbor as usize] = candidate;
+                    paths.predecessors[neighbor as usize] = vertex;
+                    queue.push(Candidate(candidate + heuristic(neighbor), neighbor));
+                }
+            }
+        }
+        paths
+    }
+}
+
+impl ShortestPaths {
+    fn new(num_vertices: u64) -> ShortestPaths {
+        ShortestPaths {
+            distances: vec![f64::INFINITY; num_vertices as usize],
+            predecessors: (0..num_vertices).collect(),
+        }
+    }
+}
+
+/// A vertex in the queue with its priority, the smallest priority comes first
+pub(crate) struct Candidate(pub(crate) f64, pub(crate) u64);
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
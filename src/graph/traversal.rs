@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+
+use crate::Graph;
+
+/// Distance of vertices which can't be reached from the source
+pub const UNREACHABLE: u64 = u64::MAX;
+
+/** Callbacks for the events of a search, see `Graph::bfs_with`
+ * and `Graph::dfs_with`. All methods do nothing by default.
+ */
+pub trait Visitor {
+    /// The vertex is reached for the first time
+    fn discover_vertex(&mut self, _vertex: u64) {}
+
+    /// All out edges of the vertex have been examined
+    fn finish_vertex(&mut self, _vertex: u64) {}
+
+    /// The edge leads to an undiscovered vertex and becomes part of the search tree
+    fn tree_edge(&mut self, _edge: u64, _source: u64, _target: u64) {}
+}
+
+/// A visitor which ignores all events
+impl Visitor for () {}
+
+/** The result of a breadth-first or depth-first search
+ *
+ * `predecessors` and `distances` have one entry per vertex. As in
+ * graph-tool, the predecessor of the source and of all vertices which
+ * were not reached is the vertex itself. Vertices which were not
+ * reached have the distance `UNREACHABLE`.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Search {
+    /// The reached vertices in the order they were discovered
+    pub order: Vec<u64>,
+    /// The parent of every vertex in the search tree
+    pub predecessors: Vec<u64>,
+    /// The depth of every vertex in the search tree (the number of
+    /// edges from the source, shortest for breadth-first search)
+    pub distances: Vec<u64>,
+}
+
+impl Search {
+    fn new(num_vertices: u64) -> Search {
+        Search {
+            order: Vec::new(),
+            predecessors: (0..num_vertices).collect(),
+            distances: vec![UNREACHABLE; num_vertices as usize],
+        }
+    }
+
+    fn discovered(&self, vertex: u64) -> bool {
+        self.distances[vertex as usize] != UNREACHABLE
+    }
+
+    fn discover(&mut self, vertex: u64, predecessor: u64, distance: u64) {
+        self.order.push(vertex);
+        self.predecessors[vertex as usize] = predecessor;
+        self.distances[vertex as usize] = distance;
+    }
+}
+
+/// Breadth-first and depth-first search.
+///
+/// The searches follow the out edges, so in undirected graphs all edges.
+/// Neighbors are visited in the order of `out_neighbors`.
+impl Graph {
+    /// Breadth-first search from `source`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let graph = Graph::try_from(data).unwrap();
+    /// let search = graph.bfs(0).unwrap();
+    /// assert_eq!(search.order[0], 0);
+    /// assert_eq!(search.distances[0], 0);
+    /// assert_eq!(search.order.len(), 77);
+    /// ```
+    pub fn bfs(&self, source: u64) -> Result<Search, String> {
+        self.bfs_with(source, &mut ())
+    }
+
+    /// Breadth-first search from `source`, calling the visitor for every event
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source does not exist
+    pub fn bfs_with<V: Visitor>(&self, source: u64, visitor: &mut V) -> Result<Search, String> {
+        self.check_vertex(source)?;
+        let mut search = Search::new(self.num_vertices());
+        let mut queue = VecDeque::new();
+
+        search.discover(source, source, 0);
+        visitor.discover_vertex(source);
+        queue.push_back(source);
+
+        while let Some(vertex) = queue.pop_front() {
+            let distance = search.distances[vertex as usize] + 1;
+            for (edge, target) in self.out_edges(vertex).zip(self.out_neighbors(vertex)) {
+                if search.discovered(target) {
+                    continue;
+                }
+                visitor.tree_edge(edge, vertex, target);
+                search.discover(target, vertex, distance);
+                visitor.discover_vertex(target);
+                queue.push_back(target);
+            }
+            visitor.finish_vertex(vertex);
+        }
+        Ok(search)
+    }
+
+    /// Depth-first search from `source`
+    ///
+    /// The search uses its own stack, so deep graphs can't overflow the
+    /// (small) wasm stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source does not exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for _ in 0..3 {
+    ///     graph.add_vertex();
+    /// }
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(1, 2).unwrap();
+    ///
+    /// let search = graph.dfs(2).unwrap();
+    /// assert_eq!(search.order, vec![2, 1, 0]);
+    /// assert_eq!(search.predecessors, vec![1, 2, 2]);
+    /// assert_eq!(search.distances, vec![2, 1, 0]);
+    /// ```
+    pub fn dfs(&self, source: u64) -> Result<Search, String> {
+        self.dfs_with(source, &mut ())
+    }
+
+    /// Depth-first search from `source`, calling the visitor for every event
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source does not exist
+    pub fn dfs_with<V: Visitor>(&self, source: u64, visitor: &mut V) -> Result<Search, String> {
+        self.check_vertex(source)?;
+        let mut search = Search::new(self.num_vertices());
+        // Vertices on the current path with their remaining out edges
+        let mut stack = vec![(
+            source,
+            self.out_edges(source).zip(self.out_neighbors(source)),
+        )];
+
+        search.discover(source, source, 0);
+        visitor.discover_vertex(source);
+
+        while let Some((vertex, out_edges)) = stack.last_mut() {
+            let vertex = *vertex;
+            match out_edges.find(|&(_, target)| !search.discovered(target)) {
+                Some((edge, target)) => {
+                    let distance = search.distances[vertex as usize] + 1;
+                    visitor.tree_edge(edge, vertex, target);
+                    search.discover(target, vertex, distance);
+                    visitor.discover_vertex(target);
+                    stack.push((
+                        target,
+                        self.out_edges(target).zip(self.out_neighbors(target)),
+                    ));
+                }
+                None => {
+                    stack.pop();
+                    visitor.finish_vertex(vertex);
+                }
+            }
+        }
+        Ok(search)
+    }
+}
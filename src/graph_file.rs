@@ -1,6 +1,33 @@
 use std::fmt::{self, Debug};
+/** Out-neighbors of all vertices in compressed sparse row layout
+ */
+pub mod adjacency;
+pub use adjacency::{Adjacency, EdgeIter, Incidence, Neighbors};
 mod io;
+pub(crate) use io::is_gt_file;
+/** Conversion between f64 and the x87 80-bit extended precision
+ * format graph-tool uses for `long double` values.
+ *
+ * In the file a long double takes 16 bytes: the 80 significant
+ * bits followed by 6 bytes of padding.
+ *
+ * Layout of the 80 bits:
+ *  [79] sign
+ *  [64:78] exponent (bias 16383)
+ *  [63] explicit integer bit
+ *  [0:62] fraction
+ */
+pub mod long_double;
+/** Options for loading a graph, e.g. which properties to load
+ */
+pub mod options;
+pub use options::LoadOptions;
 pub mod properties;
+mod reader;
+/** Summaries of gt files which are read without
+ * decoding the adjacency and the property values.
+ */
+pub mod schema;
 
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -12,7 +39,7 @@ const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
  * The file format is as follows in bytes:
  *  [0:5] magic string: 0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74
  *  [6] version number: 0x01
- *  [7] endianness: 0x00
+ *  [7] endianness: 0x00 (little endian) or 0x01 (big endian), all following values use this byte order
  *  [8:15] comment length (c): u64
  *  [16:16+c] comment: String
  *  [17+c] directed: bool
@@ -27,6 +54,7 @@ const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
  *
  *  
  */
+#[derive(Clone)]
 pub struct GraphFile {
     version_number: u8,
     endianness: u8,
@@ -35,7 +63,7 @@ pub struct GraphFile {
     pub directed: bool,
     pub num_vertices: u64,
     pub num_edges: u64,
-    pub out_neighbors: Vec<Vec<u64>>,
+    pub out_neighbors: Adjacency,
 
     // Property maps
     pub properties: Vec<properties::Property>,
@@ -51,16 +79,40 @@ impl Default for GraphFile {
         GraphFile {
             version_number: 1,
             endianness: 0,
-            comment: comment,
+            comment,
             directed: false,
             num_vertices: 0,
             num_edges: 0,
-            out_neighbors: Vec::new(),
+            out_neighbors: Adjacency::default(),
             properties: Vec::new(),
         }
     }
 }
 
+impl GraphFile {
+    /// Version of the gt format (currently always 1)
+    pub fn version(&self) -> u8 {
+        self.version_number
+    }
+
+    /// Byte order of the file: 0x00 (little endian) or 0x01 (big endian)
+    ///
+    /// Serializing the graph keeps the byte order of the file it was read from.
+    pub fn endianness(&self) -> u8 {
+        self.endianness
+    }
+
+    /// Free text comment of the file, often telling which program wrote it
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Replace the comment, it is written when the graph is serialized
+    pub fn set_comment(&mut self, comment: String) {
+        self.comment = comment;
+    }
+}
+
 impl Debug for GraphFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("GraphFile")
@@ -0,0 +1,604 @@
+use std::fmt::{self, Debug};
+
+/** Adjacency lists in compressed sparse row (CSR) layout.
+ *
+ * The out-neighbors of vertex `v` are `targets[offsets[v]..offsets[v + 1]]`,
+ * in the order of the file. In a graph read from a file, position `i` in
+ * `targets` is the edge with index `i`, i.e. the row of the edge in edge
+ * properties. Added edges get the next free index as in graph-tool, so
+ * once an edge is inserted before others the index of every position is
+ * kept in a side table (see `edge_order`).
+ *
+ * Targets are stored as u32 if every vertex index fits, otherwise as u64.
+ */
+#[derive(Clone, PartialEq)]
+pub struct Adjacency {
+    offsets: Vec<u64>,
+    targets: Targets,
+    /// The edge index of every position in `targets`, empty while it is the position
+    ids: Vec<u64>,
+    /// The position in `targets` of every edge index, the inverse of `ids`
+    positions: Vec<u64>,
+}
+
+/// Target vertices of all edges, stored with the smallest sufficient width
+#[derive(Debug, Clone, PartialEq)]
+pub enum Targets {
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+}
+
+impl Default for Adjacency {
+    fn default() -> Self {
+        Adjacency::new(0, 0)
+    }
+}
+
+impl Adjacency {
+    /// Create an empty adjacency for a graph with `num_vertices` vertices.
+    /// Vertices are appended with `push_vertex`.
+    pub(crate) fn new(num_vertices: u64, vertex_capacity: usize) -> Adjacency {
+        let mut offsets = Vec::with_capacity(vertex_capacity + 1);
+        offsets.push(0);
+        let targets = if num_vertices <= u32::MAX as u64 + 1 {
+            Targets::U32(Vec::new())
+        } else {
+            Targets::U64(Vec::new())
+        };
+        Adjacency {
+            offsets,
+            targets,
+            ids: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Add an out-neighbor to the last vertex which is not yet pushed
+    pub(crate) fn push_target(&mut self, target: u64) {
+        match &mut self.targets {
+            Targets::U32(targets) => targets.push(target as u32),
+            Targets::U64(targets) => targets.push(target),
+        }
+    }
+
+    /// Complete the out-neighbors of the next vertex
+    pub(crate) fn push_vertex(&mut self) {
+        self.offsets.push(self.num_edges());
+    }
+
+    pub(crate) fn reserve_targets(&mut self, additional: usize) {
+        match &mut self.targets {
+            Targets::U32(targets) => targets.reserve(additional),
+            Targets::U64(targets) => targets.reserve(additional),
+        }
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.offsets.shrink_to_fit();
+        match &mut self.targets {
+            Targets::U32(targets) => targets.shrink_to_fit(),
+            Targets::U64(targets) => targets.shrink_to_fit(),
+        }
+    }
+
+    /// Append a vertex without neighbors
+    pub(crate) fn add_vertex(&mut self) {
+        self.offsets.push(self.num_edges());
+        // Widen the targets if the new vertex index does not fit into u32
+        if let Targets::U32(targets) = &self.targets {
+            if self.num_vertices() > u32::MAX as u64 + 1 {
+                self.targets = Targets::U64(targets.iter().map(|&t| t as u64).collect());
+            }
+        }
+    }
+
+    /// Add an edge as last out-neighbor of `source` and return its index
+    ///
+    /// The new edge gets the index `num_edges`, the indices of the other
+    /// edges don't change. The targets of later vertices move back by one.
+    pub(crate) fn insert_edge(&mut self, source: u64, target: u64) -> u64 {
+        let index = self.num_edges();
+        let position = self.offsets[source as usize + 1];
+        if self.ids.is_empty() && position != index {
+            self.ids = (0..index).collect();
+            self.positions = (0..index).collect();
+        }
+        match &mut self.targets {
+            Targets::U32(targets) => targets.insert(position as usize, target as u32),
+            Targets::U64(targets) => targets.insert(position as usize, target),
+        }
+        for offset in &mut self.offsets[source as usize + 1..] {
+            *offset += 1;
+        }
+        if !self.ids.is_empty() {
+            self.ids.insert(position as usize, index);
+            for &id in &self.ids[position as usize + 1..] {
+                self.positions[id as usize] += 1;
+            }
+            self.positions.push(position);
+        }
+        index
+    }
+
+    /// Remove the edge with the given index
+    ///
+    /// The indices of all later edges decrease by one.
+    pub(crate) fn remove_edge(&mut self, index: u64) {
+        let Some((source, _)) = self.edge(index) else {
+            return;
+        };
+        let position = self.position(index) as usize;
+        match &mut self.targets {
+            Targets::U32(targets) => {
+                targets.remove(position);
+            }
+            Targets::U64(targets) => {
+                targets.remove(position);
+            }
+        }
+        for offset in &mut self.offsets[source as usize + 1..] {
+            *offset -= 1;
+        }
+        if !self.ids.is_empty() {
+            self.ids.remove(position);
+            for id in &mut self.ids {
+                if *id > index {
+                    *id -= 1;
+                }
+            }
+            let ids = std::mem::take(&mut self.ids);
+            self.set_ids(ids);
+        }
+    }
+
+    /// Remove a vertex and all its edges, later vertices move down by one.
+    ///
+    /// Returns for every old edge whether it is kept.
+    pub(crate) fn remove_vertex(&mut self, vertex: u64) -> Vec<bool> {
+        let keep: Vec<bool> = (0..self.num_vertices()).map(|v| v != vertex).collect();
+        self.retain_vertices(&keep)
+    }
+
+    /// Keep only the vertices for which `keep` is true and the edges between
+    /// them, the remaining vertices are renumbered in their order.
+    ///
+    /// Returns for every old edge whether it is kept. The kept edges are
+    /// renumbered in the order of their indices.
+    pub(crate) fn retain_vertices(&mut self, keep: &[bool]) -> Vec<bool> {
+        let mut kept = vec![false; self.num_edges() as usize];
+        for source in 0..self.num_vertices() {
+            for (edge, target) in self.edges(source).zip(self.get(source)) {
+                kept[edge as usize] = keep[source as usize] && keep[target as usize];
+            }
+        }
+        // New index of every kept edge
+        let mut edge_index = Vec::with_capacity(kept.len());
+        let mut num_kept_edges = 0;
+        for &k in &kept {
+            edge_index.push(num_kept_edges);
+            num_kept_edges += k as u64;
+        }
+
+        // New index of every kept vertex
+        let mut index = Vec::with_capacity(keep.len());
+        let mut num_kept = 0;
+        for &k in keep {
+            index.push(num_kept);
+            num_kept += k as u64;
+        }
+
+        let mut adjacency = Adjacency::new(num_kept, self.offsets.len() - 1);
+        adjacency.targets = match self.targets {
+            Targets::U32(_) => Targets::U32(Vec::new()),
+            Targets::U64(_) => Targets::U64(Vec::new()),
+        };
+        adjacency.reserve_targets(num_kept_edges as usize);
+        let mut ids = Vec::new();
+        for source in (0..self.num_vertices()).filter(|&v| keep[v as usize]) {
+            for (edge, target) in self.edges(source).zip(self.get(source)) {
+                if keep[target as usize] {
+                    adjacency.push_target(index[target as usize]);
+                    ids.push(edge_index[edge as usize]);
+                }
+            }
+            adjacency.push_vertex();
+        }
+        if !self.ids.is_empty() {
+            adjacency.set_ids(ids);
+        }
+        *self = adjacency;
+        kept
+    }
+
+    /// Remove all edges
+    pub(crate) fn clear_edges(&mut self) {
+        self.offsets.iter_mut().for_each(|offset| *offset = 0);
+        match &mut self.targets {
+            Targets::U32(targets) => targets.clear(),
+            Targets::U64(targets) => targets.clear(),
+        }
+        self.ids.clear();
+        self.positions.clear();
+    }
+
+    /// Use `ids` as edge index of every position, the side table is
+    /// dropped if it is the identity
+    fn set_ids(&mut self, ids: Vec<u64>) {
+        if ids
+            .iter()
+            .enumerate()
+            .all(|(position, &id)| id == position as u64)
+        {
+            self.ids = Vec::new();
+            self.positions = Vec::new();
+            return;
+        }
+        self.positions = vec![0; ids.len()];
+        for (position, &id) in ids.iter().enumerate() {
+            self.positions[id as usize] = position as u64;
+        }
+        self.ids = ids;
+    }
+
+    /// The position in `targets` of the edge with the given index
+    fn position(&self, index: u64) -> u64 {
+        match self.positions.get(index as usize) {
+            Some(&position) => position,
+            None => index,
+        }
+    }
+
+    /// Build the adjacency from one list of out-neighbors per vertex
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::graph_file::Adjacency;
+    ///
+    /// let adjacency = Adjacency::from_lists(&[vec![1, 2], vec![], vec![0]]);
+    /// assert_eq!(adjacency.offsets(), &[0, 2, 2, 3]);
+    /// assert_eq!(adjacency.get(2).to_vec(), vec![0]);
+    /// ```
+    pub fn from_lists(lists: &[Vec<u64>]) -> Adjacency {
+        let mut adjacency = Adjacency::new(lists.len() as u64, lists.len());
+        adjacency.reserve_targets(lists.iter().map(|list| list.len()).sum());
+        for list in lists {
+            for &target in list {
+                adjacency.push_target(target);
+            }
+            adjacency.push_vertex();
+        }
+        adjacency
+    }
+
+    pub fn num_vertices(&self) -> u64 {
+        self.offsets.len() as u64 - 1
+    }
+
+    pub fn num_edges(&self) -> u64 {
+        self.targets().len() as u64
+    }
+
+    /// Number of out-neighbors of the vertex `v`
+    pub fn degree(&self, v: u64) -> u64 {
+        self.offsets[v as usize + 1] - self.offsets[v as usize]
+    }
+
+    /// Out-neighbors of the vertex `v`
+    pub fn get(&self, v: u64) -> Neighbors<'_> {
+        let start = self.offsets[v as usize] as usize;
+        let end = self.offsets[v as usize + 1] as usize;
+        match &self.targets {
+            Targets::U32(targets) => Neighbors::U32(&targets[start..end]),
+            Targets::U64(targets) => Neighbors::U64(&targets[start..end]),
+        }
+    }
+
+    /// Indices of the out edges of the vertex `v`, in the order of `get`
+    ///
+    /// They are consecutive unless edges were inserted before others.
+    pub fn edges(&self, v: u64) -> EdgeIter<'_> {
+        let range = self.offsets[v as usize]..self.offsets[v as usize + 1];
+        match self.ids.is_empty() {
+            true => EdgeIter::Range(range),
+            false => EdgeIter::Slice(self.ids[range.start as usize..range.end as usize].iter()),
+        }
+    }
+
+    /// Source and target of the edge with the given index
+    pub fn edge(&self, index: u64) -> Option<(u64, u64)> {
+        if index >= self.num_edges() {
+            return None;
+        }
+        let position = self.position(index);
+        let target = self.targets().get(position as usize)?;
+        let source = self.offsets.partition_point(|&offset| offset <= position) - 1;
+        Some((source as u64, target))
+    }
+
+    /// The edge index of every position in `targets`, `None` if it is the
+    /// position (as in a graph read from a file)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add_vertex();
+    /// graph.add_vertex();
+    /// graph.add_edge(1, 0).unwrap();
+    /// assert_eq!(graph.adjacency().edge_order(), None);
+    ///
+    /// // The new edge is stored before the one of vertex 1
+    /// graph.add_edge(0, 1).unwrap();
+    /// assert_eq!(graph.adjacency().edge_order(), Some(&[1, 0][..]));
+    /// ```
+    pub fn edge_order(&self) -> Option<&[u64]> {
+        match self.ids.is_empty() {
+            true => None,
+            false => Some(&self.ids),
+        }
+    }
+
+    /// Offsets into the targets, one per vertex plus the total number of edges
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
+
+    /// Targets of all edges, by source
+    pub fn targets(&self) -> Neighbors<'_> {
+        match &self.targets {
+            Targets::U32(targets) => Neighbors::U32(targets),
+            Targets::U64(targets) => Neighbors::U64(targets),
+        }
+    }
+
+    /// Iterate over the out-neighbors of all vertices
+    pub fn iter(&self) -> impl Iterator<Item = Neighbors<'_>> {
+        (0..self.num_vertices()).map(move |v| self.get(v))
+    }
+}
+
+impl Debug for Adjacency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/** Neighbors of every vertex together with the index of the
+ * connecting edge, for orders other than the CSR order of the file.
+ *
+ * Built from an `Adjacency` either reversed (the in-neighbors of
+ * every vertex) or undirected (the out-neighbors followed by the
+ * in-neighbors). Both keep the order of the adjacency per direction.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Incidence {
+    adjacency: Adjacency,
+    edges: Vec<u64>,
+}
+
+impl Incidence {
+    /// In-neighbors of every vertex of the adjacency
+    pub fn reversed(adjacency: &Adjacency) -> Incidence {
+        Incidence::build(adjacency, false)
+    }
+
+    /// Out-neighbors followed by in-neighbors of every vertex of the adjacency.
+    ///
+    /// As in graph-tool, a self-loop is listed twice.
+    pub fn undirected(adjacency: &Adjacency) -> Incidence {
+        Incidence::build(adjacency, true)
+    }
+
+    fn build(adjacency: &Adjacency, out: bool) -> Incidence {
+        let num_vertices = adjacency.num_vertices() as usize;
+
+        // Count the entries of every vertex, then turn the counts into offsets
+        let mut offsets = vec![0u64; num_vertices + 1];
+        for (source, neighbors) in adjacency.iter().enumerate() {
+            if out {
+                offsets[source + 1] += neighbors.len() as u64;
+            }
+            for target in neighbors {
+                offsets[target as usize + 1] += 1;
+            }
+        }
+        for v in 0..num_vertices {
+            offsets[v + 1] += offsets[v];
+        }
+
+        let len = offsets[num_vertices] as usize;
+        let mut neighbors = match adjacency.targets {
+            Targets::U32(_) => Targets::U32(vec![0; len]),
+            Targets::U64(_) => Targets::U64(vec![0; len]),
+        };
+        let mut edges = vec![0u64; len];
+        let mut next = offsets.clone();
+        let mut place = |v: u64, neighbor: u64, edge: u64| {
+            let position = next[v as usize] as usize;
+            next[v as usize] += 1;
+            match &mut neighbors {
+                Targets::U32(neighbors) => neighbors[position] = neighbor as u32,
+                Targets::U64(neighbors) => neighbors[position] = neighbor,
+            }
+            edges[position] = edge;
+        };
+
+        // All out entries of a vertex come before its in entries
+        if out {
+            for source in 0..adjacency.num_vertices() {
+                for (edge, target) in adjacency.edges(source).zip(adjacency.get(source)) {
+                    place(source, target, edge);
+                }
+            }
+        }
+        for source in 0..adjacency.num_vertices() {
+            for (edge, target) in adjacency.edges(source).zip(adjacency.get(source)) {
+                place(target, source, edge);
+            }
+        }
+
+        Incidence {
+            adjacency: Adjacency {
+                offsets,
+                targets: neighbors,
+                ids: Vec::new(),
+                positions: Vec::new(),
+            },
+            edges,
+        }
+    }
+
+    pub fn degree(&self, v: u64) -> u64 {
+        self.adjacency.degree(v)
+    }
+
+    /// Neighbors of the vertex `v`
+    pub fn neighbors(&self, v: u64) -> Neighbors<'_> {
+        self.adjacency.get(v)
+    }
+
+    /// Indices of the edges to the neighbors of the vertex `v`
+    pub fn edges(&self, v: u64) -> &[u64] {
+        let offsets = self.adjacency.offsets();
+        &self.edges[offsets[v as usize] as usize..offsets[v as usize + 1] as usize]
+    }
+}
+
+/** Slice of vertex indices, e.g. the out-neighbors of a vertex.
+ *
+ * Depending on the number of vertices, indices are stored as u32 or u64.
+ * Iterating always yields u64.
+ */
+#[derive(Clone, Copy)]
+pub enum Neighbors<'a> {
+    U32(&'a [u32]),
+    U64(&'a [u64]),
+}
+
+impl<'a> Neighbors<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            Neighbors::U32(slice) => slice.len(),
+            Neighbors::U64(slice) => slice.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u64> {
+        match self {
+            Neighbors::U32(slice) => slice.get(index).map(|&v| v as u64),
+            Neighbors::U64(slice) => slice.get(index).copied(),
+        }
+    }
+
+    pub fn contains(&self, vertex: u64) -> bool {
+        self.iter().any(|v| v == vertex)
+    }
+
+    pub fn iter(&self) -> NeighborsIter<'a> {
+        match *self {
+            Neighbors::U32(slice) => NeighborsIter::U32(slice.iter()),
+            Neighbors::U64(slice) => NeighborsIter::U64(slice.iter()),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u64> {
+        self.iter().collect()
+    }
+}
+
+impl Debug for Neighbors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Neighbors<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl PartialEq<[u64]> for Neighbors<'_> {
+    fn eq(&self, other: &[u64]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl<'a> IntoIterator for Neighbors<'a> {
+    type Item = u64;
+    type IntoIter = NeighborsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over `Neighbors`
+#[derive(Clone)]
+pub enum NeighborsIter<'a> {
+    U32(std::slice::Iter<'a, u32>),
+    U64(std::slice::Iter<'a, u64>),
+}
+
+impl Iterator for NeighborsIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        match self {
+            NeighborsIter::U32(iter) => iter.next().map(|&v| v as u64),
+            NeighborsIter::U64(iter) => iter.next().copied(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            NeighborsIter::U32(iter) => iter.size_hint(),
+            NeighborsIter::U64(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl DoubleEndedIterator for NeighborsIter<'_> {
+    fn next_back(&mut self) -> Option<u64> {
+        match self {
+            NeighborsIter::U32(iter) => iter.next_back().map(|&v| v as u64),
+            NeighborsIter::U64(iter) => iter.next_back().copied(),
+        }
+    }
+}
+
+impl ExactSizeIterator for NeighborsIter<'_> {}
+
+/// Iterator over edge indices
+#[derive(Clone)]
+pub enum EdgeIter<'a> {
+    Range(std::ops::Range<u64>),
+    Slice(std::slice::Iter<'a, u64>),
+}
+
+impl Iterator for EdgeIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        match self {
+            EdgeIter::Range(iter) => iter.next(),
+            EdgeIter::Slice(iter) => iter.next().copied(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            EdgeIter::Range(iter) => iter.size_hint(),
+            EdgeIter::Slice(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for EdgeIter<'_> {}
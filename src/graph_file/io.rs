@@ -1,122 +1,458 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
-use super::GraphFile;
-use crate::graph_file::properties::Property;
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
+
+use super::reader::{Discard, Reader, Skip};
+use super::schema::{GraphHeader, GraphSchema};
+use super::{GraphFile, LoadOptions};
+use crate::error::{GtError, Section};
+use crate::graph_file::properties::{FileBuffer, Property, PropertyMapType};
+use crate::graph_file::Adjacency;
+
+const MAGIC_STRING: [u8; 6] = [0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74];
 
 impl TryFrom<&[u8]> for GraphFile {
-    type Error = String;
+    type Error = GtError;
 
     fn try_from(file: &[u8]) -> Result<Self, Self::Error> {
-        let mut cursor = std::io::Cursor::new(file);
-        let magic_string = &[0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74];
-        if file.len() < 14 {
-            return Err("file is too short".into());
-        }
+        read_file(Reader::from_slice(file), None, &LoadOptions::default())
+    }
+}
 
-        // Read magic string with cursor
-        let mut magic = vec![0; 6];
-        cursor.read_exact(&mut magic).unwrap();
-        if &magic[0..6] != magic_string {
-            return Err("Invalid file header".into());
-        }
+impl GraphFile {
+    /// Parse an uncompressed gt file from any reader
+    ///
+    /// The file is parsed while it is read, so the reader can e.g. be a
+    /// streaming decompressor and the decompressed file is never held in
+    /// memory as a whole. Use `Graph::from_reader` to detect and decode
+    /// compressed input.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the file is invalid. Errors of the reader
+    /// itself are reported as `GtError::Decode`.
+    pub fn from_reader<R: Read>(reader: R) -> Result<GraphFile, GtError> {
+        GraphFile::from_reader_with(reader, &LoadOptions::default())
+    }
 
-        // Read version number
-        let version_number = cursor.read_u8().unwrap();
-        if version_number != 0x01 {
-            return Err("Invalid file header: version not supported (yet)".into());
-        }
+    /// Same as [`GraphFile::from_reader`], loading only what the options ask for
+    ///
+    /// Properties which are not loaded are read and dropped without decoding them.
+    pub fn from_reader_with<R: Read>(
+        reader: R,
+        options: &LoadOptions,
+    ) -> Result<GraphFile, GtError> {
+        read_file(Reader::new(Discard(reader)), None, options)
+    }
 
-        // Read endianness
-        let endianness = cursor.read_u8().unwrap();
-        if endianness != 0x00 {
-            return Err("Invalid file header: only little endianess  supported (yet)".into());
+    /// Parse an uncompressed gt file and keep it to decode the properties lazily
+    ///
+    /// Only the adjacency is decoded while loading. For every property the
+    /// location of its values is recorded, the values are decoded when they
+    /// are first accessed. Fixed width values (bool, int16_t, int32_t, int64_t
+    /// and double) in native byte order are not copied at all if they are
+    /// aligned in the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the file is invalid, the same as `GraphFile::try_from`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::GraphFile;
+    /// use gt_graph_wasm::decode::decodebuffer;
+    ///
+    /// let data = decodebuffer(include_bytes!("../../test_data/network.gt.zst")).unwrap();
+    /// let graph_file = GraphFile::from_buffer(data).unwrap();
+    /// let label = graph_file.properties.iter().find(|p| p.name == "label").unwrap();
+    /// assert!(!label.is_loaded());
+    /// assert_eq!(label.as_strings().unwrap()[0], "Myriel");
+    /// assert!(label.is_loaded());
+    /// ```
+    pub fn from_buffer(data: Vec<u8>) -> Result<GraphFile, GtError> {
+        GraphFile::from_buffer_with(data, &LoadOptions::default())
+    }
+
+    /// Same as [`GraphFile::from_buffer`], loading only what the options ask for
+    pub fn from_buffer_with(data: Vec<u8>, options: &LoadOptions) -> Result<GraphFile, GtError> {
+        let (_, endianness) = read_prelude(&mut Reader::from_slice(&data))?;
+        let file = FileBuffer::new(data, endianness == 0x01);
+        read_file(Reader::from_slice(file.data()), Some(&file), options)
+    }
+}
+
+impl GraphFile {
+    /// Read only the header of an uncompressed gt file
+    ///
+    /// Only the first bytes (up to the number of vertices) are parsed,
+    /// so this is cheap even for huge files.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the header is invalid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::GraphFile;
+    /// use gt_graph_wasm::decode::decodebuffer;
+    ///
+    /// let data = decodebuffer(include_bytes!("../../test_data/network.gt.zst")).unwrap();
+    /// let header = GraphFile::read_header(&data).unwrap();
+    /// assert_eq!(header.num_vertices, 77);
+    /// assert!(!header.directed);
+    /// ```
+    pub fn read_header(data: &[u8]) -> Result<GraphHeader, GtError> {
+        GraphFile::read_header_from_reader(data)
+    }
+
+    /// Same as [`GraphFile::read_header`] for any reader, the reader is
+    /// not read beyond the header
+    pub fn read_header_from_reader<R: Read>(reader: R) -> Result<GraphHeader, GtError> {
+        let mut reader = Reader::new(reader);
+        let (version, endianness) = read_prelude(&mut reader)?;
+        match endianness {
+            0x01 => read_header(
+                &mut reader.with_byte_order::<BigEndian>(),
+                version,
+                endianness,
+            ),
+            _ => read_header(&mut reader, version, endianness),
         }
+    }
+
+    /// Read the header, the number of edges and the property schema
+    /// of an uncompressed gt file without decoding any data
+    ///
+    /// The adjacency lists and the property values are skipped. Fixed width
+    /// values are skipped at once, for lists and strings only their length
+    /// is read. Nothing is allocated for the skipped data.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the file is invalid or truncated. Unlike
+    /// `GraphFile::try_from` the neighbor indices are not checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::GraphFile;
+    /// use gt_graph_wasm::decode::decodebuffer;
+    ///
+    /// let data = decodebuffer(include_bytes!("../../test_data/network.gt.zst")).unwrap();
+    /// let schema = GraphFile::read_schema(&data).unwrap();
+    /// assert_eq!(schema.num_edges, 254);
+    ///
+    /// let value = schema.properties.iter().find(|p| p.name == "value").unwrap();
+    /// assert_eq!(value.len, 254);
+    /// assert_eq!(value.size, 254 * 2);
+    /// ```
+    pub fn read_schema(data: &[u8]) -> Result<GraphSchema, GtError> {
+        read_schema(Reader::from_slice(data))
+    }
+
+    /// Same as [`GraphFile::read_schema`] for any reader
+    ///
+    /// Readers can't seek, so the skipped data is read and dropped.
+    pub fn read_schema_from_reader<R: Read>(reader: R) -> Result<GraphSchema, GtError> {
+        read_schema(Reader::new(Discard(reader)))
+    }
+}
+
+/// Whether the data starts like an uncompressed gt file
+pub(crate) fn is_gt_file(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC_STRING)
+}
+
+/// Read the magic string, the version and the endianness
+fn read_prelude<R: Read>(reader: &mut Reader<R, LittleEndian>) -> Result<(u8, u8), GtError> {
+    // Read magic string
+    let magic = reader.read_bytes(6)?;
+    if magic != MAGIC_STRING {
+        return Err(GtError::BadMagic {
+            offset: 0,
+            found: magic,
+        });
+    }
+
+    // Read version number
+    let offset = reader.offset();
+    let version_number = reader.read_u8()?;
+    if version_number != 0x01 {
+        return Err(GtError::UnsupportedVersion {
+            offset,
+            version: version_number,
+        });
+    }
+
+    // Read endianness, everything after it is stored in this byte order
+    let offset = reader.offset();
+    let endianness = reader.read_u8()?;
+    if endianness > 0x01 {
+        return Err(GtError::UnsupportedEndianness { offset, endianness });
+    }
+
+    Ok((version_number, endianness))
+}
+
+/// Read a gt file, the properties are decoded lazily if the
+/// reader reads `file`, otherwise they are decoded right away
+fn read_file<R: Read + Skip>(
+    mut reader: Reader<R, LittleEndian>,
+    file: Option<&FileBuffer>,
+    options: &LoadOptions,
+) -> Result<GraphFile, GtError> {
+    let (version, endianness) = read_prelude(&mut reader)?;
+    match endianness {
+        0x01 => read_body(
+            reader.with_byte_order::<BigEndian>(),
+            version,
+            endianness,
+            file,
+            options,
+        ),
+        _ => read_body(reader, version, endianness, file, options),
+    }
+}
+
+/// Read the comment, the direction and the number of vertices
+fn read_header<R: Read, B: ByteOrder>(
+    reader: &mut Reader<R, B>,
+    version: u8,
+    endianness: u8,
+) -> Result<GraphHeader, GtError> {
+    // Read comment
+    reader.section = Section::Comment;
+    let comment = reader.read_string()?;
+
+    // Read directed
+    reader.section = Section::Header;
+    let directed = reader.read_u8()? == 0x01;
+
+    // Read number of vertices
+    let num_vertices = reader.read_u64()?;
 
-        // Read comment
-        let c_len = cursor.read_u64::<LittleEndian>().unwrap() as usize;
-        let mut comment = vec![0; c_len];
-        cursor.read_exact(&mut comment).unwrap();
-        let comment = String::from_utf8_lossy(&comment).to_string();
-
-        // Read directed
-        let directed = cursor.read_u8().unwrap() == 0x01;
-
-        // Read number of vertices
-        let num_vertices = cursor.read_u64::<LittleEndian>().unwrap();
-
-        // Read neighbor list
-        let out_neighbors = get_out_neighbors(&mut cursor, num_vertices);
-
-        // Calculate number of edges
-        let num_edges: u64 = out_neighbors
-            .iter()
-            .map(|v| v.len())
-            .sum::<usize>()
-            .try_into()
-            .unwrap();
-
-        // Parse properties
-        let num_properties = cursor.read_u64::<LittleEndian>().unwrap();
-
-        let properties: Vec<Property> = (0..num_properties)
-            .into_iter()
-            .map(|_| Property::from_data(&mut cursor, num_vertices, num_edges))
-            .collect::<Result<Vec<Property>, String>>()?;
-
-        let gf = GraphFile {
-            version_number: version_number,
-            endianness: endianness,
-            comment: comment,
-            directed: directed,
-            num_vertices: num_vertices,
-            num_edges: num_edges,
-            out_neighbors: out_neighbors,
-            properties: properties,
+    Ok(GraphHeader {
+        version,
+        endianness,
+        comment,
+        directed,
+        num_vertices,
+    })
+}
+
+/// Read everything after the endianness byte
+fn read_body<R: Read + Skip, B: ByteOrder>(
+    mut reader: Reader<R, B>,
+    version: u8,
+    endianness: u8,
+    file: Option<&FileBuffer>,
+    options: &LoadOptions,
+) -> Result<GraphFile, GtError> {
+    let header = read_header(&mut reader, version, endianness)?;
+    let num_vertices = header.num_vertices;
+
+    // Read neighbor list
+    reader.section = Section::Adjacency;
+    let out_neighbors = get_out_neighbors(&mut reader, num_vertices)?;
+
+    // Calculate number of edges
+    let num_edges = out_neighbors.num_edges();
+
+    // Parse properties
+    reader.section = Section::PropertyHeader;
+    let num_properties = reader.read_u64()?;
+
+    let mut properties: Vec<Property> = Vec::new();
+    for _ in 0..num_properties {
+        let property = match file {
+            Some(file) => {
+                Property::from_buffer(&mut reader, num_vertices, num_edges, file, options)?
+            }
+            None => Property::from_data(&mut reader, num_vertices, num_edges, options)?,
         };
+        properties.extend(property);
+    }
+
+    let gf = GraphFile {
+        version_number: header.version,
+        endianness: header.endianness,
+        comment: header.comment,
+        directed: header.directed,
+        num_vertices,
+        num_edges,
+        out_neighbors,
+        properties,
+    };
+
+    Ok(gf)
+}
 
-        Ok(gf)
+fn read_schema<R: Read + Skip>(
+    mut reader: Reader<R, LittleEndian>,
+) -> Result<GraphSchema, GtError> {
+    let (version, endianness) = read_prelude(&mut reader)?;
+    match endianness {
+        0x01 => read_schema_body(reader.with_byte_order::<BigEndian>(), version, endianness),
+        _ => read_schema_body(reader, version, endianness),
     }
 }
 
-fn get_out_neighbors(cursor: &mut std::io::Cursor<&[u8]>, num_vertices: u64) -> Vec<Vec<u64>> {
-    let s_t;
-    match num_vertices {
-        n if n <= u8::MAX as u64 => {
-            s_t = std::mem::size_of::<u8>();
-        }
-        n if n <= u16::MAX as u64 => {
-            s_t = std::mem::size_of::<u16>();
+/// Like `read_body`, but the adjacency and the property values are skipped
+fn read_schema_body<R: Read + Skip, B: ByteOrder>(
+    mut reader: Reader<R, B>,
+    version: u8,
+    endianness: u8,
+) -> Result<GraphSchema, GtError> {
+    let header = read_header(&mut reader, version, endianness)?;
+    let num_vertices = header.num_vertices;
+
+    // Only the number of neighbors of each vertex is read
+    reader.section = Section::Adjacency;
+    let index_size = index_size(num_vertices) as u64;
+    let mut num_edges: u64 = 0;
+    for _ in 0..num_vertices {
+        let num_neighbors = reader.read_u64()?;
+        reader.skip(num_neighbors.saturating_mul(index_size))?;
+        num_edges += num_neighbors;
+    }
+
+    reader.section = Section::PropertyHeader;
+    let num_properties = reader.read_u64()?;
+    let properties = (0..num_properties)
+        .map(|_| Property::skip_data(&mut reader, num_vertices, num_edges))
+        .collect::<Result<Vec<_>, GtError>>()?;
+
+    Ok(GraphSchema {
+        header,
+        num_edges,
+        properties,
+    })
+}
+
+impl GraphFile {
+    /// Serialize the graph into the gt binary format
+    ///
+    /// The layout mirrors the one read by `GraphFile::try_from`.
+    /// Values are written in the byte order given by the endianness of the file.
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.endianness {
+            0x01 => self.write_with_byte_order::<BigEndian, W>(writer),
+            _ => self.write_with_byte_order::<LittleEndian, W>(writer),
         }
-        n if n <= u32::MAX as u64 => {
-            s_t = std::mem::size_of::<u32>();
+    }
+
+    /// Serialize the graph into a new buffer
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        // Writing into a vector can not fail
+        self.write(&mut data).unwrap();
+        data
+    }
+}
+
+impl GraphFile {
+    fn write_with_byte_order<B: ByteOrder, W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        // Header
+        writer.write_all(&MAGIC_STRING)?;
+        writer.write_u8(self.version_number)?;
+        writer.write_u8(self.endianness)?;
+        writer.write_u64::<B>(self.comment.len() as u64)?;
+        writer.write_all(self.comment.as_bytes())?;
+        writer.write_u8(self.directed as u8)?;
+        writer.write_u64::<B>(self.num_vertices)?;
+
+        // Neighbor list
+        write_out_neighbors::<B, W>(writer, &self.out_neighbors, self.num_vertices)?;
+
+        // Properties
+        writer.write_u64::<B>(self.properties.len() as u64)?;
+        for property in &self.properties {
+            match self.out_neighbors.edge_order() {
+                // Edge values are stored in the order of the neighbor list
+                Some(order) if property.map_type == PropertyMapType::Edge => {
+                    property.load().map_err(std::io::Error::other)?;
+                    let mut sorted = property.clone();
+                    sorted.permute_rows(order);
+                    sorted.write_data::<B, W>(writer)?;
+                }
+                _ => property.write_data::<B, W>(writer)?,
+            }
         }
-        _ => {
-            s_t = std::mem::size_of::<u64>();
+        Ok(())
+    }
+}
+
+/// Number of bytes used to store a vertex index in the neighbor list
+fn index_size(num_vertices: u64) -> usize {
+    match num_vertices {
+        n if n <= u8::MAX as u64 => std::mem::size_of::<u8>(),
+        n if n <= u16::MAX as u64 => std::mem::size_of::<u16>(),
+        n if n <= u32::MAX as u64 => std::mem::size_of::<u32>(),
+        _ => std::mem::size_of::<u64>(),
+    }
+}
+
+fn write_out_neighbors<B: ByteOrder, W: Write>(
+    writer: &mut W,
+    out_neighbors: &Adjacency,
+    num_vertices: u64,
+) -> std::io::Result<()> {
+    let s_t = index_size(num_vertices);
+
+    for neighbors in out_neighbors.iter() {
+        writer.write_u64::<B>(neighbors.len() as u64)?;
+        for neighbor in neighbors {
+            match s_t {
+                1 => writer.write_u8(neighbor as u8)?,
+                2 => writer.write_u16::<B>(neighbor as u16)?,
+                4 => writer.write_u32::<B>(neighbor as u32)?,
+                _ => writer.write_u64::<B>(neighbor)?,
+            }
         }
     }
+    Ok(())
+}
 
-    let mut out_neighbors: Vec<Vec<u64>> = Vec::new();
+fn get_out_neighbors<R: Read, B: ByteOrder>(
+    reader: &mut Reader<R, B>,
+    num_vertices: u64,
+) -> Result<Adjacency, GtError> {
+    let s_t = index_size(num_vertices);
 
-    for _ in 0..num_vertices {
+    // Every vertex stores at least its number of neighbors (u64)
+    let capacity = reader.capacity_of(num_vertices, 8);
+    let mut out_neighbors = Adjacency::new(num_vertices, capacity);
+
+    for vertex in 0..num_vertices {
         // Get num neighbors for each node
-        let num_neighbors = cursor.read_u64::<LittleEndian>().unwrap() as usize;
+        let num_neighbors = reader.read_u64()?;
 
         // Get neighbors
-        let mut neighbors = Vec::with_capacity(num_neighbors);
+        out_neighbors.reserve_targets(reader.capacity_of(num_neighbors, s_t as u64));
         for _ in 0..num_neighbors {
+            let offset = reader.offset();
             let neighbor = match s_t {
-                1 => cursor.read_u8().unwrap() as u64,
-                2 => cursor.read_u16::<LittleEndian>().unwrap() as u64,
-                4 => cursor.read_u32::<LittleEndian>().unwrap() as u64,
-                8 => cursor.read_u64::<LittleEndian>().unwrap() as u64,
-                _ => panic!("Invalid size of type"),
+                1 => reader.read_u8()? as u64,
+                2 => reader.read_u16()? as u64,
+                4 => reader.read_u32()? as u64,
+                _ => reader.read_u64()?,
             };
-            neighbors.push(neighbor);
+            if neighbor >= num_vertices {
+                return Err(GtError::NeighborOutOfRange {
+                    offset,
+                    vertex,
+                    neighbor,
+                    num_vertices,
+                });
+            }
+            out_neighbors.push_target(neighbor);
         }
 
-        out_neighbors.push(neighbors);
+        out_neighbors.push_vertex();
     }
+    out_neighbors.shrink_to_fit();
 
-    out_neighbors
+    Ok(out_neighbors)
 }
@@ -0,0 +1,100 @@
+const F64_EXPONENT_BIAS: i64 = 1023;
+const EXPONENT_BIAS: i64 = 16383;
+const EXPONENT_MAX: u128 = 0x7fff;
+
+/// Encode a f64 as x87 extended precision (exact, every f64 is representable)
+pub fn encode(value: f64) -> u128 {
+    let bits = value.to_bits();
+    let sign = (bits >> 63) as u128;
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+
+    let (exponent, mantissa): (u128, u64) = match exponent {
+        // Zero
+        0 if fraction == 0 => (0, 0),
+        // Subnormal f64 values are normal in the extended format
+        0 => {
+            let shift = fraction.leading_zeros() as i64;
+            let exponent = EXPONENT_BIAS + 63 - 1074 - shift;
+            (exponent as u128, fraction << shift)
+        }
+        // Infinity and NaN
+        0x7ff => (EXPONENT_MAX, (1 << 63) | (fraction << 11)),
+        _ => {
+            let exponent = exponent - F64_EXPONENT_BIAS + EXPONENT_BIAS;
+            (exponent as u128, (1 << 63) | (fraction << 11))
+        }
+    };
+
+    (sign << 79) | (exponent << 64) | mantissa as u128
+}
+
+/// Decode x87 extended precision to the nearest f64
+///
+/// The 64 bit mantissa is rounded to 53 bits (round half to even), so up to
+/// 11 bits of precision are lost. Values beyond the f64 range become
+/// infinite, tiny values become f64 denormals or zero. Infinities keep
+/// their sign and every NaN is mapped to a quiet NaN with the same sign
+/// and the upper bits of its payload.
+pub fn decode(value: u128) -> f64 {
+    let sign = ((value >> 79) & 1) as u64;
+    let exponent = ((value >> 64) & EXPONENT_MAX) as i64;
+    let mantissa = value as u64;
+    let signed = |bits: u64| f64::from_bits((sign << 63) | bits);
+
+    // Infinity and NaN (the integer bit is ignored)
+    if exponent == EXPONENT_MAX as i64 {
+        let fraction = mantissa & !(1 << 63);
+        if fraction == 0 {
+            return signed(0x7ff << 52);
+        }
+        return signed((0x7ff << 52) | (1 << 51) | (fraction >> 11));
+    }
+
+    if mantissa == 0 {
+        return signed(0);
+    }
+
+    // Normalize such that the integer bit is set
+    // (extended denormals use the same exponent as the smallest normals)
+    let shift = mantissa.leading_zeros() as i64;
+    let mantissa = mantissa << shift;
+    let mut exponent = exponent.max(1) - EXPONENT_BIAS - shift;
+
+    if exponent > F64_EXPONENT_BIAS {
+        return signed(0x7ff << 52);
+    }
+
+    // Number of mantissa bits that do not fit into the f64
+    let dropped = if exponent >= 1 - F64_EXPONENT_BIAS {
+        11
+    } else {
+        11 + (1 - F64_EXPONENT_BIAS - exponent)
+    };
+    if dropped > 64 {
+        return signed(0);
+    }
+
+    let mantissa = mantissa as u128;
+    let mut rounded = (mantissa >> dropped) as u64;
+    let remainder = mantissa & ((1 << dropped) - 1);
+    let half = 1 << (dropped - 1);
+    if remainder > half || (remainder == half && rounded & 1 == 1) {
+        rounded += 1;
+    }
+
+    if dropped > 11 {
+        // Denormal (rounding up may yield the smallest normal, which has the same bits)
+        return signed(rounded);
+    }
+
+    if rounded == 1 << 53 {
+        rounded >>= 1;
+        exponent += 1;
+        if exponent > F64_EXPONENT_BIAS {
+            return signed(0x7ff << 52);
+        }
+    }
+    let biased = (exponent + F64_EXPONENT_BIAS) as u64;
+    signed((biased << 52) | (rounded & ((1 << 52) - 1)))
+}
@@ -0,0 +1,51 @@
+/// Options for loading a graph, see `Graph::from_bytes_with`
+///
+/// # Example
+///
+/// ```
+/// use gt_graph_wasm::LoadOptions;
+///
+/// let options = LoadOptions::default().with_properties(["_pos", "label"]);
+/// assert!(options.includes("label"));
+/// assert!(!options.includes("value"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadOptions {
+    /// Names of the properties to load, all properties are loaded if `None`.
+    ///
+    /// Other properties are skipped without decoding them. Names which
+    /// are not in the file are ignored.
+    pub properties: Option<Vec<String>>,
+    /// Keep the decompressed file and decode every property on its first
+    /// access instead of while loading, see `Graph::from_bytes_with`.
+    ///
+    /// Loading is faster and properties which are never accessed cost
+    /// nothing, but the whole decompressed file stays in memory as long
+    /// as the graph exists. That is why it is off by default.
+    pub lazy: bool,
+}
+
+impl LoadOptions {
+    /// Load only the properties with the given names
+    pub fn with_properties<I, S>(mut self, names: I) -> LoadOptions
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.properties = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Decode the properties on first access, see `LoadOptions::lazy`
+    pub fn with_lazy(mut self, lazy: bool) -> LoadOptions {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Whether the property with the given name is loaded
+    pub fn includes(&self, name: &str) -> bool {
+        self.properties
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|included| included == name))
+    }
+}
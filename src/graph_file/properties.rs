@@ -1,11 +1,20 @@
-extern crate byteorder;
+use std::any::Any;
+use std::cell::OnceCell;
 use std::fmt::{self, Debug};
-use std::io::Read;
+use std::io::{Read, Write};
+use std::ops::Deref;
+use std::sync::Arc;
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
 use wasm_bindgen::JsValue;
 
-#[derive(PartialEq)]
+use super::long_double;
+use super::options::LoadOptions;
+use super::reader::{Reader, Skip};
+use super::schema::PropertySchema;
+use crate::error::{GtError, Section};
+
+#[derive(PartialEq, Clone, Copy)]
 pub enum PropertyMapType {
     Graph,
     Vertex,
@@ -24,6 +33,16 @@ impl TryFrom<u8> for PropertyMapType {
     }
 }
 
+impl From<&PropertyMapType> for u8 {
+    fn from(value: &PropertyMapType) -> Self {
+        match value {
+            PropertyMapType::Graph => 0x00,
+            PropertyMapType::Vertex => 0x01,
+            PropertyMapType::Edge => 0x02,
+        }
+    }
+}
+
 impl Debug for PropertyMapType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -36,7 +55,10 @@ impl Debug for PropertyMapType {
 
 // No idea if the following is the most elegant way but it works
 // There might be a way to do the same with generics in less lines
-enum PropertyType {
+/// Value type of a property map, see the
+/// [gt format](https://graph-tool.skewed.de/static/doc/gt_format.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyType {
     Bool,
     Int16,
     Int32,
@@ -58,28 +80,468 @@ enum PropertyType {
  * a vector even if it is a graph property
  * (length 1)
  */
+#[derive(PartialEq, Clone)]
 enum PropertyData {
-    Bool(Vec<u8>), //no bool vec in js
-    Int16(Vec<i16>),
-    Int32(Vec<i32>),
-    Int64(Vec<i64>),
-    Double(Vec<f64>),
-    LongDouble(Vec<f64>), //no long double in js nor rust
+    Bool(Scalars<u8>), //no bool vec in js
+    Int16(Scalars<i16>),
+    Int32(Scalars<i32>),
+    Int64(Scalars<i64>),
+    Double(Scalars<f64>),
+    LongDouble(LongDoubles), //no long double in js nor rust
     String(Vec<String>),
     VectorBool(Vec<Vec<u8>>),
     VectorInt16(Vec<Vec<i16>>),
     VectorInt32(Vec<Vec<i32>>),
     VectorInt64(Vec<Vec<i64>>),
     VectorDouble(Vec<Vec<f64>>),
-    VectorLongDouble(Vec<Vec<f64>>), //There is no long double in js nor rust
+    VectorLongDouble(Vec<LongDoubles>), //There is no long double in js nor rust
     VectorString(Vec<Vec<String>>),
     PyObject(Vec<Vec<u8>>),
 }
 
+impl PropertyData {
+    /// Empty data of the given type with room for `capacity` values
+    fn with_capacity(value_type: PropertyType, capacity: usize) -> PropertyData {
+        match value_type {
+            PropertyType::Bool => PropertyData::Bool(Scalars::with_capacity(capacity)),
+            PropertyType::Int16 => PropertyData::Int16(Scalars::with_capacity(capacity)),
+            PropertyType::Int32 => PropertyData::Int32(Scalars::with_capacity(capacity)),
+            PropertyType::Int64 => PropertyData::Int64(Scalars::with_capacity(capacity)),
+            PropertyType::Double => PropertyData::Double(Scalars::with_capacity(capacity)),
+            PropertyType::LongDouble => {
+                PropertyData::LongDouble(LongDoubles::with_capacity(capacity))
+            }
+            PropertyType::String => PropertyData::String(Vec::with_capacity(capacity)),
+            PropertyType::VectorBool => PropertyData::VectorBool(Vec::with_capacity(capacity)),
+            PropertyType::VectorInt16 => PropertyData::VectorInt16(Vec::with_capacity(capacity)),
+            PropertyType::VectorInt32 => PropertyData::VectorInt32(Vec::with_capacity(capacity)),
+            PropertyType::VectorInt64 => PropertyData::VectorInt64(Vec::with_capacity(capacity)),
+            PropertyType::VectorDouble => PropertyData::VectorDouble(Vec::with_capacity(capacity)),
+            PropertyType::VectorLongDouble => {
+                PropertyData::VectorLongDouble(Vec::with_capacity(capacity))
+            }
+            PropertyType::VectorString => PropertyData::VectorString(Vec::with_capacity(capacity)),
+            PropertyType::PyObject => PropertyData::PyObject(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Number of values (one per vertex or edge, one for graph properties)
+    fn len(&self) -> usize {
+        match self {
+            PropertyData::Bool(v) => v.len(),
+            PropertyData::Int16(v) => v.len(),
+            PropertyData::Int32(v) => v.len(),
+            PropertyData::Int64(v) => v.len(),
+            PropertyData::Double(v) => v.len(),
+            PropertyData::LongDouble(v) => v.raw.len(),
+            PropertyData::String(v) => v.len(),
+            PropertyData::VectorBool(v) => v.len(),
+            PropertyData::VectorInt16(v) => v.len(),
+            PropertyData::VectorInt32(v) => v.len(),
+            PropertyData::VectorInt64(v) => v.len(),
+            PropertyData::VectorDouble(v) => v.len(),
+            PropertyData::VectorLongDouble(v) => v.len(),
+            PropertyData::VectorString(v) => v.len(),
+            PropertyData::PyObject(v) => v.len(),
+        }
+    }
+
+    /// Approximate memory used by the values (including the
+    /// headers of strings and vectors, excluding unused capacity)
+    fn size_bytes(&self) -> usize {
+        fn fixed<T>(values: &[T]) -> usize {
+            std::mem::size_of_val(values)
+        }
+        fn nested<T>(values: &[Vec<T>]) -> usize {
+            values
+                .iter()
+                .map(|row| std::mem::size_of::<Vec<T>>() + fixed(row))
+                .sum()
+        }
+        fn strings(values: &[String]) -> usize {
+            values
+                .iter()
+                .map(|value| std::mem::size_of::<String>() + value.len())
+                .sum()
+        }
+
+        match self {
+            PropertyData::Bool(v) => fixed(v),
+            PropertyData::Int16(v) => fixed(v),
+            PropertyData::Int32(v) => fixed(v),
+            PropertyData::Int64(v) => fixed(v),
+            PropertyData::Double(v) => fixed(v),
+            PropertyData::LongDouble(v) => fixed(&v.values) + fixed(&v.raw),
+            PropertyData::String(v) => strings(v),
+            PropertyData::VectorBool(v) | PropertyData::PyObject(v) => nested(v),
+            PropertyData::VectorInt16(v) => nested(v),
+            PropertyData::VectorInt32(v) => nested(v),
+            PropertyData::VectorInt64(v) => nested(v),
+            PropertyData::VectorDouble(v) => nested(v),
+            PropertyData::VectorLongDouble(v) => v
+                .iter()
+                .map(|row| {
+                    std::mem::size_of::<LongDoubles>() + fixed(&row.values) + fixed(&row.raw)
+                })
+                .sum(),
+            PropertyData::VectorString(v) => v
+                .iter()
+                .map(|row| std::mem::size_of::<Vec<String>>() + strings(row))
+                .sum(),
+        }
+    }
+
+    /// Replace the values with `values` (a `Vec` of an [`Element`] type)
+    /// if they fit the value type
+    fn replace(&mut self, values: Box<dyn Any>) -> bool {
+        match self {
+            PropertyData::Bool(v) => replace_with(v.to_mut(), values),
+            PropertyData::Int16(v) => replace_with(v.to_mut(), values),
+            PropertyData::Int32(v) => replace_with(v.to_mut(), values),
+            PropertyData::Int64(v) => replace_with(v.to_mut(), values),
+            PropertyData::Double(v) => replace_with(v.to_mut(), values),
+            PropertyData::LongDouble(v) => match values.downcast::<Vec<f64>>() {
+                Ok(values) => {
+                    *v = LongDoubles::from_f64(*values);
+                    true
+                }
+                Err(_) => false,
+            },
+            PropertyData::String(v) => replace_with(v, values),
+            PropertyData::VectorBool(v) => replace_with(v, values),
+            PropertyData::VectorInt16(v) => replace_with(v, values),
+            PropertyData::VectorInt32(v) => replace_with(v, values),
+            PropertyData::VectorInt64(v) => replace_with(v, values),
+            PropertyData::VectorDouble(v) => replace_with(v, values),
+            PropertyData::VectorLongDouble(v) => match values.downcast::<Vec<Vec<f64>>>() {
+                Ok(values) => {
+                    *v = values.into_iter().map(LongDoubles::from_f64).collect();
+                    true
+                }
+                Err(_) => false,
+            },
+            PropertyData::VectorString(v) => replace_with(v, values),
+            PropertyData::PyObject(v) => replace_with(v, values),
+        }
+    }
+
+    fn value_type(&self) -> PropertyType {
+        match self {
+            PropertyData::Bool(_) => PropertyType::Bool,
+            PropertyData::Int16(_) => PropertyType::Int16,
+            PropertyData::Int32(_) => PropertyType::Int32,
+            PropertyData::Int64(_) => PropertyType::Int64,
+            PropertyData::Double(_) => PropertyType::Double,
+            PropertyData::LongDouble(_) => PropertyType::LongDouble,
+            PropertyData::String(_) => PropertyType::String,
+            PropertyData::VectorBool(_) => PropertyType::VectorBool,
+            PropertyData::VectorInt16(_) => PropertyType::VectorInt16,
+            PropertyData::VectorInt32(_) => PropertyType::VectorInt32,
+            PropertyData::VectorInt64(_) => PropertyType::VectorInt64,
+            PropertyData::VectorDouble(_) => PropertyType::VectorDouble,
+            PropertyData::VectorLongDouble(_) => PropertyType::VectorLongDouble,
+            PropertyData::VectorString(_) => PropertyType::VectorString,
+            PropertyData::PyObject(_) => PropertyType::PyObject,
+        }
+    }
+
+    fn column(&mut self) -> &mut dyn Column {
+        match self {
+            PropertyData::Bool(v) => v.to_mut(),
+            PropertyData::Int16(v) => v.to_mut(),
+            PropertyData::Int32(v) => v.to_mut(),
+            PropertyData::Int64(v) => v.to_mut(),
+            PropertyData::Double(v) => v.to_mut(),
+            PropertyData::LongDouble(v) => v,
+            PropertyData::String(v) => v,
+            PropertyData::VectorBool(v) => v,
+            PropertyData::VectorInt16(v) => v,
+            PropertyData::VectorInt32(v) => v,
+            PropertyData::VectorInt64(v) => v,
+            PropertyData::VectorDouble(v) => v,
+            PropertyData::VectorLongDouble(v) => v,
+            PropertyData::VectorString(v) => v,
+            PropertyData::PyObject(v) => v,
+        }
+    }
+}
+
+/// Pickled `None`, the default value of python object properties in graph-tool
+const PY_NONE: &[u8] = b"\x80\x02N.";
+
+/** A list of property values (one per vertex or edge)
+ * which can be edited row by row.
+ */
+trait Column {
+    /// Insert the default value (zero or empty) at `index`
+    fn insert_default(&mut self, index: usize);
+    /// Keep only the rows for which `keep` returns true
+    fn retain_rows(&mut self, keep: &mut dyn FnMut(usize) -> bool);
+    /// Reorder the rows, row `i` becomes the old row `order[i]`
+    fn permute_rows(&mut self, order: &[u64]);
+}
+
+impl<T: Default> Column for Vec<T> {
+    fn insert_default(&mut self, index: usize) {
+        self.insert(index, T::default());
+    }
+
+    fn retain_rows(&mut self, keep: &mut dyn FnMut(usize) -> bool) {
+        let mut row = 0;
+        self.retain(|_| {
+            row += 1;
+            keep(row - 1)
+        });
+    }
+
+    fn permute_rows(&mut self, order: &[u64]) {
+        let mut rows = std::mem::take(self);
+        *self = order
+            .iter()
+            .map(|&row| std::mem::take(&mut rows[row as usize]))
+            .collect();
+    }
+}
+
+impl Column for LongDoubles {
+    fn insert_default(&mut self, index: usize) {
+        self.values.insert(index, 0.0);
+        self.raw.insert(index, 0);
+    }
+
+    fn retain_rows(&mut self, keep: &mut dyn FnMut(usize) -> bool) {
+        let mut kept = Vec::with_capacity(self.raw.len());
+        for row in 0..self.raw.len() {
+            kept.push(keep(row));
+        }
+        self.values.retain_rows(&mut |row| kept[row]);
+        self.raw.retain_rows(&mut |row| kept[row]);
+    }
+
+    fn permute_rows(&mut self, order: &[u64]) {
+        self.values.permute_rows(order);
+        self.raw.permute_rows(order);
+    }
+}
+
+/** Long double values, kept as the raw extended precision
+ * values from the file (to write them back without loss)
+ * next to their f64 approximation (which is handed to js).
+ */
+#[derive(PartialEq, Clone, Default)]
+struct LongDoubles {
+    values: Vec<f64>,
+    raw: Vec<u128>,
+}
+
+impl LongDoubles {
+    fn with_capacity(capacity: usize) -> LongDoubles {
+        LongDoubles {
+            values: Vec::with_capacity(capacity),
+            raw: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Exact long double values of the given f64 values
+    fn from_f64(values: Vec<f64>) -> LongDoubles {
+        LongDoubles {
+            raw: values
+                .iter()
+                .map(|value| long_double::encode(*value))
+                .collect(),
+            values,
+        }
+    }
+
+    fn push(&mut self, raw: u128) {
+        self.values.push(long_double::decode(raw));
+        self.raw.push(raw);
+    }
+
+    /// Copy of the raw values as little endian bytes (16 per value)
+    fn raw_bytes(&self) -> js_sys::Uint8Array {
+        let bytes: Vec<u8> = self.raw.iter().flat_map(|raw| raw.to_le_bytes()).collect();
+        js_sys::Uint8Array::from(bytes.as_slice())
+    }
+}
+
+/** Fixed width values which are either owned or, right after loading,
+ * borrowed from the file they were read from (zero copy). Borrowed
+ * values are copied into an owned vector before they are changed.
+ */
+#[derive(Clone)]
+enum Scalars<T> {
+    Owned(Vec<T>),
+    /// `len` values starting at `offset` in `file`, only created by
+    /// `Scalars::map` which checks the alignment
+    Mapped {
+        file: Arc<Vec<u8>>,
+        offset: usize,
+        len: usize,
+    },
+}
+
+/// Types which can be read from any (aligned) bytes in native byte order
+///
+/// # Safety
+///
+/// Every bit pattern has to be a valid value of the type.
+unsafe trait Plain: Copy {}
+
+unsafe impl Plain for u8 {}
+unsafe impl Plain for i16 {}
+unsafe impl Plain for i32 {}
+unsafe impl Plain for i64 {}
+unsafe impl Plain for f64 {}
+
+impl<T> Scalars<T> {
+    fn with_capacity(capacity: usize) -> Scalars<T> {
+        Scalars::Owned(Vec::with_capacity(capacity))
+    }
+
+    fn as_slice(&self) -> &[T] {
+        match self {
+            Scalars::Owned(v) => v,
+            // Safety: `Scalars::map` checked that the values are in bounds and
+            // aligned, and that `T` is `Plain`. The file is never changed.
+            Scalars::Mapped { file, offset, len } => unsafe {
+                std::slice::from_raw_parts(file.as_ptr().add(*offset) as *const T, *len)
+            },
+        }
+    }
+}
+
+impl<T: Plain> Scalars<T> {
+    /// Borrow `len` values at `offset` from the file (stored in native
+    /// byte order), if they are aligned for `T`
+    fn map(file: &Arc<Vec<u8>>, offset: usize, len: usize) -> Option<Scalars<T>> {
+        let size = len.checked_mul(std::mem::size_of::<T>())?;
+        let aligned = (file.as_ptr() as usize + offset).is_multiple_of(std::mem::align_of::<T>());
+        (aligned && offset.checked_add(size)? <= file.len()).then(|| Scalars::Mapped {
+            file: file.clone(),
+            offset,
+            len,
+        })
+    }
+
+    /// The values as an owned vector, borrowed values are copied first
+    fn to_mut(&mut self) -> &mut Vec<T> {
+        if let Scalars::Mapped { .. } = self {
+            *self = Scalars::Owned(self.as_slice().to_vec());
+        }
+        match self {
+            Scalars::Owned(v) => v,
+            Scalars::Mapped { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<T> Deref for Scalars<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Scalars<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/** The decompressed file a graph was read from. It is kept to
+ * decode the values of its properties on first use.
+ */
+#[derive(Clone)]
+pub(crate) struct FileBuffer {
+    data: Arc<Vec<u8>>,
+    big_endian: bool,
+}
+
+impl FileBuffer {
+    pub(crate) fn new(data: Vec<u8>, big_endian: bool) -> FileBuffer {
+        FileBuffer {
+            data: Arc::new(data),
+            big_endian,
+        }
+    }
+
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/** The location of the values of a property in the file,
+ * they are decoded when they are first needed.
+ */
+#[derive(Clone)]
+struct Encoded {
+    file: FileBuffer,
+    value_type: PropertyType,
+    len: usize,
+    offset: usize,
+    size: usize,
+}
+
+impl Encoded {
+    /// Decode the values, fixed width values in native byte order
+    /// are borrowed from the file if they are aligned
+    fn decode(&self) -> Result<PropertyData, GtError> {
+        match self.map() {
+            Some(data) => Ok(data),
+            None => match self.file.big_endian {
+                true => self.read::<BigEndian>(),
+                false => self.read::<LittleEndian>(),
+            },
+        }
+    }
+
+    fn map(&self) -> Option<PropertyData> {
+        if self.file.big_endian != cfg!(target_endian = "big") {
+            return None;
+        }
+        let (file, offset, len) = (&self.file.data, self.offset, self.len);
+        match self.value_type {
+            PropertyType::Bool => Scalars::map(file, offset, len).map(PropertyData::Bool),
+            PropertyType::Int16 => Scalars::map(file, offset, len).map(PropertyData::Int16),
+            PropertyType::Int32 => Scalars::map(file, offset, len).map(PropertyData::Int32),
+            PropertyType::Int64 => Scalars::map(file, offset, len).map(PropertyData::Int64),
+            PropertyType::Double => Scalars::map(file, offset, len).map(PropertyData::Double),
+            _ => None,
+        }
+    }
+
+    fn read<B: ByteOrder>(&self) -> Result<PropertyData, GtError> {
+        let mut reader = Reader::<_, B>::from_slice(self.bytes());
+        read_values(&mut reader, self.value_type, self.len as u64)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.file.data[self.offset..self.offset + self.size]
+    }
+}
+
+/** A property map: one value per vertex or edge, or a single value
+ * for graph properties.
+ *
+ * Properties of graphs loaded from a buffer (see `GraphFile::from_buffer`)
+ * are decoded when their values are first accessed.
+ */
+#[derive(Clone)]
 pub struct Property {
     pub name: String,
     pub map_type: PropertyMapType,
-    data: PropertyData,
+    /// The values, set on first access for properties that are not decoded yet
+    data: OnceCell<PropertyData>,
+    /// Where the values are in the file, dropped once the values change
+    encoded: Option<Encoded>,
+}
+
+impl PartialEq for Property {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.map_type == other.map_type && self.data() == other.data()
+    }
 }
 
 impl Debug for Property {
@@ -91,326 +553,719 @@ impl Debug for Property {
     }
 }
 
+/** A single value of a property, borrowed from the property.
+ *
+ * Long doubles are rounded to the nearest f64 and python
+ * objects are the pickled bytes.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyValue<'a> {
+    Bool(bool),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Double(f64),
+    LongDouble(f64),
+    String(&'a str),
+    VectorBool(&'a [u8]),
+    VectorInt16(&'a [i16]),
+    VectorInt32(&'a [i32]),
+    VectorInt64(&'a [i64]),
+    VectorDouble(&'a [f64]),
+    VectorLongDouble(&'a [f64]),
+    VectorString(&'a [String]),
+    PyObject(&'a [u8]),
+}
+
+/// Metadata of a property, see [`Property::info`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyInfo {
+    pub name: String,
+    pub map_type: PropertyMapType,
+    pub value_type: PropertyType,
+    /// Number of values
+    pub len: usize,
+    /// Approximate memory used by the values in bytes
+    /// (their size in the file if they are not decoded yet)
+    pub bytes: usize,
+    /// Whether the values are decoded, see [`Property::is_loaded`]
+    pub loaded: bool,
+}
+
 impl Property {
+    /// Create a property with `len` default values (zero, empty or `None`)
+    pub(crate) fn new(
+        name: &str,
+        map_type: PropertyMapType,
+        value_type: PropertyType,
+        len: usize,
+    ) -> Property {
+        let mut property = Property {
+            name: name.to_string(),
+            map_type,
+            data: OnceCell::from(PropertyData::with_capacity(value_type, len)),
+            encoded: None,
+        };
+        for index in 0..len {
+            property.insert_default(index);
+        }
+        property
+    }
+
     /** Create a property from data
-     * given a cursor and the length of the property
+     * given a reader and the length of the property.
+     * Properties which are not included in the options are
+     * skipped and `None` is returned.
      */
-    pub fn from_data(
-        cursor: &mut std::io::Cursor<&[u8]>,
+    pub(crate) fn from_data<R: Read + Skip, B: ByteOrder>(
+        reader: &mut Reader<R, B>,
         num_vertices: u64,
         num_edges: u64,
-    ) -> Result<Property, String> {
-        // Property map type
-        let property_map_type = cursor.read_u8().unwrap();
-        let property_map_type = PropertyMapType::try_from(property_map_type)?;
-
+        options: &LoadOptions,
+    ) -> Result<Option<Property>, GtError> {
+        let (property_map_type, name, property_type) = Property::read_header(reader)?;
         let length = match property_map_type {
             PropertyMapType::Graph => 1,
             PropertyMapType::Vertex => num_vertices,
             PropertyMapType::Edge => num_edges,
         };
+        if !options.includes(&name) {
+            skip_values(reader, property_type, length)?;
+            return Ok(None);
+        }
+
+        let data = read_values(reader, property_type, length)?;
+        let property: Property = Property {
+            name,
+            data: OnceCell::from(data),
+            map_type: property_map_type,
+            encoded: None,
+        };
+        Ok(Some(property))
+    }
+
+    /** Create a property whose values are decoded on first access.
+     * The reader has to read `file`, the values are skipped and
+     * only their location is kept. Properties which are not
+     * included in the options are dropped (`None`).
+     */
+    pub(crate) fn from_buffer<R: Read + Skip, B: ByteOrder>(
+        reader: &mut Reader<R, B>,
+        num_vertices: u64,
+        num_edges: u64,
+        file: &FileBuffer,
+        options: &LoadOptions,
+    ) -> Result<Option<Property>, GtError> {
+        let schema = Property::skip_data(reader, num_vertices, num_edges)?;
+        if !options.includes(&schema.name) {
+            return Ok(None);
+        }
+        Ok(Some(Property {
+            name: schema.name,
+            map_type: schema.map_type,
+            data: OnceCell::new(),
+            encoded: Some(Encoded {
+                file: file.clone(),
+                value_type: schema.value_type,
+                len: schema.len as usize,
+                offset: schema.offset as usize,
+                size: schema.size as usize,
+            }),
+        }))
+    }
+
+    /// Decode the values if they are not decoded yet
+    ///
+    /// The accessors of `Graph` (e.g. `Graph::property`) load a property
+    /// before they return it, such that values which can not be decoded
+    /// are reported as an error. The methods of a property which was not
+    /// loaded decode it on first use and panic in that case.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GtError` if the values in the file are invalid
+    pub fn load(&self) -> Result<(), GtError> {
+        if let (None, Some(encoded)) = (self.data.get(), &self.encoded) {
+            // Can't be set in between, the cell is not shared
+            let _ = self.data.set(encoded.decode()?);
+        }
+        Ok(())
+    }
+
+    /// The values, decoded on first access
+    fn data(&self) -> &PropertyData {
+        self.data.get_or_init(|| match &self.encoded {
+            Some(encoded) => encoded.decode().unwrap_or_else(|error| {
+                panic!("property '{}' can not be decoded: {}", self.name, error)
+            }),
+            None => unreachable!("property '{}' has no values", self.name),
+        })
+    }
+
+    /// The values for changing them, the location in the file is dropped
+    fn data_mut(&mut self) -> &mut PropertyData {
+        self.data();
+        self.encoded = None;
+        self.data.get_mut().unwrap()
+    }
+
+    /// Whether the values are decoded, see [`Property::info`]
+    pub fn is_loaded(&self) -> bool {
+        self.data.get().is_some()
+    }
+
+    /// Read the map type, name and value type of a property
+    fn read_header<R: Read, B: ByteOrder>(
+        reader: &mut Reader<R, B>,
+    ) -> Result<(PropertyMapType, String, PropertyType), GtError> {
+        reader.section = Section::PropertyHeader;
+
+        // Property map type
+        let offset = reader.offset();
+        let property_map_type = reader.read_u8()?;
+        let property_map_type = PropertyMapType::try_from(property_map_type).map_err(|_| {
+            GtError::UnknownPropertyMapType {
+                offset,
+                section: reader.section.clone(),
+                value: property_map_type,
+            }
+        })?;
 
         // Property name
-        let name_size = cursor.read_u64::<LittleEndian>().unwrap();
-        let mut name = vec![0; name_size as usize];
-        cursor.read_exact(&mut name).unwrap();
-        let name = String::from_utf8_lossy(&name).to_string();
+        let name = reader.read_string()?;
+        reader.section = Section::Property(name.clone());
 
         // Property type
-        let property_type = cursor.read_u8().unwrap();
-        let property_type = PropertyType::try_from(property_type)?;
-
-        // Initialize the property data array
-        let mut property_data = match property_type {
-            PropertyType::Bool => PropertyData::Bool(Vec::with_capacity(length as usize)),
-            PropertyType::Int16 => PropertyData::Int16(Vec::with_capacity(length as usize)),
-            PropertyType::Int32 => PropertyData::Int32(Vec::with_capacity(length as usize)),
-            PropertyType::Int64 => PropertyData::Int64(Vec::with_capacity(length as usize)),
-            PropertyType::Double => PropertyData::Double(Vec::with_capacity(length as usize)),
-            PropertyType::LongDouble => {
-                PropertyData::LongDouble(Vec::with_capacity(length as usize))
-            }
-            PropertyType::String => PropertyData::String(Vec::with_capacity(length as usize)),
-            PropertyType::VectorBool => {
-                PropertyData::VectorBool(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorInt16 => {
-                PropertyData::VectorInt16(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorInt32 => {
-                PropertyData::VectorInt32(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorInt64 => {
-                PropertyData::VectorInt64(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorDouble => {
-                PropertyData::VectorDouble(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorLongDouble => {
-                PropertyData::VectorLongDouble(Vec::with_capacity(length as usize))
-            }
-            PropertyType::VectorString => {
-                PropertyData::VectorString(Vec::with_capacity(length as usize))
-            }
-            PropertyType::PyObject => PropertyData::PyObject(Vec::with_capacity(length as usize)),
+        let offset = reader.offset();
+        let property_type = reader.read_u8()?;
+        let property_type =
+            PropertyType::try_from(property_type).map_err(|_| GtError::UnknownPropertyType {
+                offset,
+                section: reader.section.clone(),
+                value: property_type,
+            })?;
+
+        Ok((property_map_type, name, property_type))
+    }
+
+    /** Read the header of a property and skip over its values
+     * without decoding them. Fixed width values are skipped at
+     * once, for other values only the lengths are read.
+     */
+    pub(crate) fn skip_data<R: Read + Skip, B: ByteOrder>(
+        reader: &mut Reader<R, B>,
+        num_vertices: u64,
+        num_edges: u64,
+    ) -> Result<PropertySchema, GtError> {
+        let (map_type, name, value_type) = Property::read_header(reader)?;
+        let len = match map_type {
+            PropertyMapType::Graph => 1,
+            PropertyMapType::Vertex => num_vertices,
+            PropertyMapType::Edge => num_edges,
         };
-        //let property_data = property_data;
-        // Fill the array
-        for _ in 0..length {
-            match &mut property_data {
-                PropertyData::Bool(v) => {
-                    // read the bool
-                    let bool = cursor.read_u8().unwrap();
-                    // Convert to bool
-                    v.push(bool);
-                }
-                PropertyData::Int16(v) => {
-                    let int16 = cursor.read_i16::<LittleEndian>().unwrap();
-                    v.push(int16);
+
+        let offset = reader.offset();
+        skip_values(reader, value_type, len)?;
+
+        Ok(PropertySchema {
+            name,
+            map_type,
+            value_type,
+            len,
+            offset,
+            size: reader.offset() - offset,
+        })
+    }
+
+    /** Serialize the property in the gt format
+     * (map type, name, value type and the values)
+     */
+    pub(crate) fn write_data<B: ByteOrder, W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_u8(u8::from(&self.map_type))?;
+        write_string::<B, W>(writer, &self.name)?;
+        writer.write_u8(u8::from(self.value_type()))?;
+
+        // Values which were not decoded are copied as they are
+        if let (None, Some(encoded)) = (self.data.get(), &self.encoded) {
+            if encoded.file.big_endian == is_big_endian::<B>() {
+                return writer.write_all(encoded.bytes());
+            }
+        }
+        self.load().map_err(std::io::Error::other)?;
+
+        match self.data() {
+            PropertyData::Bool(v) => writer.write_all(v)?,
+            PropertyData::Int16(v) => {
+                for value in v.iter() {
+                    writer.write_i16::<B>(*value)?;
                 }
-                PropertyData::Int32(v) => {
-                    let int32 = cursor.read_i32::<LittleEndian>().unwrap();
-                    v.push(int32);
+            }
+            PropertyData::Int32(v) => {
+                for value in v.iter() {
+                    writer.write_i32::<B>(*value)?;
                 }
-                PropertyData::Int64(v) => {
-                    let int64 = cursor.read_i64::<LittleEndian>().unwrap();
-                    v.push(int64);
+            }
+            PropertyData::Int64(v) => {
+                for value in v.iter() {
+                    writer.write_i64::<B>(*value)?;
                 }
-                PropertyData::Double(v) => {
-                    let double = cursor.read_f64::<LittleEndian>().unwrap();
-                    v.push(double);
+            }
+            PropertyData::Double(v) => {
+                for value in v.iter() {
+                    writer.write_f64::<B>(*value)?;
                 }
-                PropertyData::LongDouble(v) => {
-                    let long_double = cursor.read_u128::<LittleEndian>().unwrap();
-                    // Convert to f64 (should work am not sure though)
-                    let long_double = long_double as f64;
-                    v.push(long_double);
+            }
+            PropertyData::LongDouble(v) => {
+                for value in &v.raw {
+                    writer.write_u128::<B>(*value)?;
                 }
-                PropertyData::String(v) => {
-                    let string_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut string = vec![0; string_length as usize];
-                    cursor.read_exact(&mut string).unwrap();
-                    let string = String::from_utf8_lossy(&string).to_string();
-                    v.push(string);
+            }
+            PropertyData::String(v) => {
+                for value in v {
+                    write_string::<B, W>(writer, value)?;
                 }
-                PropertyData::VectorBool(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    cursor.read_exact(&mut vector).unwrap();
-                    v.push(vector);
+            }
+            PropertyData::VectorBool(v) | PropertyData::PyObject(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    writer.write_all(vector)?;
                 }
-                PropertyData::VectorInt16(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector: Vec<i16> = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let int16 = cursor.read_i16::<LittleEndian>().unwrap();
-                        vector.push(int16);
+            }
+            PropertyData::VectorInt16(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    for value in vector {
+                        writer.write_i16::<B>(*value)?;
                     }
-                    v.push(vector);
                 }
-                PropertyData::VectorInt32(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let int32 = cursor.read_i32::<LittleEndian>().unwrap();
-                        vector.push(int32);
+            }
+            PropertyData::VectorInt32(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    for value in vector {
+                        writer.write_i32::<B>(*value)?;
                     }
-                    v.push(vector);
                 }
-                PropertyData::VectorInt64(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let int64 = cursor.read_i64::<LittleEndian>().unwrap();
-                        vector.push(int64);
+            }
+            PropertyData::VectorInt64(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    for value in vector {
+                        writer.write_i64::<B>(*value)?;
                     }
-                    v.push(vector);
                 }
-                PropertyData::VectorDouble(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let double = cursor.read_f64::<LittleEndian>().unwrap();
-                        vector.push(double);
+            }
+            PropertyData::VectorDouble(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    for value in vector {
+                        writer.write_f64::<B>(*value)?;
                     }
-                    v.push(vector);
                 }
-                PropertyData::VectorLongDouble(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let long_double = cursor.read_u128::<LittleEndian>().unwrap();
-                        let long_double = long_double as f64;
-                        vector.push(long_double);
+            }
+            PropertyData::VectorLongDouble(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.raw.len() as u64)?;
+                    for value in &vector.raw {
+                        writer.write_u128::<B>(*value)?;
                     }
-                    v.push(vector);
                 }
-                PropertyData::VectorString(v) => {
-                    let vector_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut vector = Vec::with_capacity(vector_length as usize);
-                    for _ in 0..vector_length {
-                        let string_length = cursor.read_u64::<LittleEndian>().unwrap();
-                        let mut string = vec![0; string_length as usize];
-                        cursor.read_exact(&mut string).unwrap();
-                        let string = String::from_utf8_lossy(&string).to_string();
-                        vector.push(string);
+            }
+            PropertyData::VectorString(v) => {
+                for vector in v {
+                    writer.write_u64::<B>(vector.len() as u64)?;
+                    for value in vector {
+                        write_string::<B, W>(writer, value)?;
                     }
-                    v.push(vector);
-                }
-                PropertyData::PyObject(v) => {
-                    let object_length = cursor.read_u64::<LittleEndian>().unwrap();
-                    let mut object = vec![0; object_length as usize];
-                    cursor.read_exact(&mut object).unwrap();
-                    v.push(object);
                 }
             }
         }
+        Ok(())
+    }
 
-        // Static data with leaky box
-        //let data: &'a PropertyData = Box::leak(property_data.into_boxed_slice());
+    /// Number of values (the number of vertices or edges, one for graph properties)
+    pub fn len(&self) -> usize {
+        match (self.data.get(), &self.encoded) {
+            (None, Some(encoded)) => encoded.len,
+            _ => self.data().len(),
+        }
+    }
 
-        // Read the size of the property
-        // This depends further on the type of the property and the length
-        let property: Property = Property {
-            name,
-            data: property_data,
-            map_type: property_map_type,
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The type of the values
+    pub fn value_type(&self) -> PropertyType {
+        match (self.data.get(), &self.encoded) {
+            (None, Some(encoded)) => encoded.value_type,
+            _ => self.data().value_type(),
+        }
+    }
+
+    /// Name, types, length and size of the property (without decoding the values)
+    pub fn info(&self) -> PropertyInfo {
+        let bytes = match (self.data.get(), &self.encoded) {
+            (None, Some(encoded)) => encoded.size,
+            _ => self.data().size_bytes(),
         };
-        Ok(property)
+        PropertyInfo {
+            name: self.name.clone(),
+            map_type: self.map_type,
+            value_type: self.value_type(),
+            len: self.len(),
+            bytes,
+            loaded: self.is_loaded(),
+        }
     }
 
-    /// Get the data as a JsValue
-    /// This is used to return the data to js
-    /// Note that the data is not copied
-    pub fn data_view(&mut self) -> JsValue {
-        match &mut self.data {
-            PropertyData::Bool(v) => unsafe {
-                let array = js_sys::Uint8Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::Int16(v) => unsafe {
-                let array = js_sys::Int16Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::Int32(v) => unsafe {
-                let array = js_sys::Int32Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::Int64(v) => unsafe {
-                let array = js_sys::BigInt64Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::Double(v) => unsafe {
-                let array = js_sys::Float64Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::LongDouble(v) => unsafe {
-                let array = js_sys::Float64Array::view_mut_raw(v.as_mut_ptr(), v.len());
-                array.into()
-            },
-            PropertyData::String(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter().enumerate() {
-                    array.set(i as u32, JsValue::from_str(value));
-                }
-                array.into()
+    /// Get all values as a slice of `T`
+    ///
+    /// `T` has to match the value type of the property (see [`Element`]), e.g.
+    /// `f64` for double and long double (rounded) properties or `Vec<i32>` for
+    /// `vector<int32_t>` properties. Vector long double properties can only be
+    /// read value by value (see [`Property::value`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` does not match the value type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let mut graph = Graph::try_from(data).unwrap();
+    ///
+    /// let property = graph.edge_property("value".to_string()).unwrap();
+    /// let values = property.get::<i16>().unwrap();
+    /// assert_eq!(values.len(), 254);
+    /// assert!(property.get::<f64>().is_err());
+    /// ```
+    pub fn get<T: Element>(&self) -> Result<&[T], String> {
+        let mismatch = || {
+            format!(
+                "Property '{}' has values of type {}, not {}",
+                self.name,
+                self.value_type().name(),
+                std::any::type_name::<T>()
+            )
+        };
+        // Different value types can share the same storage, e.g. vector<bool>
+        // and python::object are both `Vec<Vec<u8>>`
+        if !T::VALUE_TYPES.contains(&self.value_type()) {
+            return Err(mismatch());
+        }
+        let values: &dyn Any = match self.data() {
+            PropertyData::Bool(v) => v,
+            PropertyData::Int16(v) => v,
+            PropertyData::Int32(v) => v,
+            PropertyData::Int64(v) => v,
+            PropertyData::Double(v) => v,
+            PropertyData::LongDouble(v) => &v.values,
+            PropertyData::String(v) => v,
+            PropertyData::VectorBool(v) => v,
+            PropertyData::VectorInt16(v) => v,
+            PropertyData::VectorInt32(v) => v,
+            PropertyData::VectorInt64(v) => v,
+            PropertyData::VectorDouble(v) => v,
+            PropertyData::VectorLongDouble(v) => v,
+            PropertyData::VectorString(v) => v,
+            PropertyData::PyObject(v) => v,
+        };
+        values
+            .downcast_ref::<Scalars<T>>()
+            .map(Scalars::as_slice)
+            .or_else(|| values.downcast_ref::<Vec<T>>().map(Vec::as_slice))
+            .ok_or_else(mismatch)
+    }
+
+    /// Bool values (0 or 1), one byte each as in the file
+    pub fn as_bools(&self) -> Option<&[u8]> {
+        self.get().ok()
+    }
+
+    pub fn as_i16_slice(&self) -> Option<&[i16]> {
+        self.get().ok()
+    }
+
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        self.get().ok()
+    }
+
+    pub fn as_i64_slice(&self) -> Option<&[i64]> {
+        self.get().ok()
+    }
+
+    /// Double values, long doubles are rounded to the nearest f64
+    pub fn as_f64_slice(&self) -> Option<&[f64]> {
+        self.get().ok()
+    }
+
+    pub fn as_strings(&self) -> Option<&[String]> {
+        self.get().ok()
+    }
+
+    pub fn as_vec_bool(&self) -> Option<&[Vec<u8>]> {
+        match self.data() {
+            PropertyData::VectorBool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_vec_i16(&self) -> Option<&[Vec<i16>]> {
+        self.get().ok()
+    }
+
+    pub fn as_vec_i32(&self) -> Option<&[Vec<i32>]> {
+        self.get().ok()
+    }
+
+    pub fn as_vec_i64(&self) -> Option<&[Vec<i64>]> {
+        self.get().ok()
+    }
+
+    pub fn as_vec_f64(&self) -> Option<&[Vec<f64>]> {
+        self.get().ok()
+    }
+
+    pub fn as_vec_strings(&self) -> Option<&[Vec<String>]> {
+        self.get().ok()
+    }
+
+    /// Pickled python objects
+    pub fn as_objects(&self) -> Option<&[Vec<u8>]> {
+        match self.data() {
+            PropertyData::PyObject(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// The value of a single vertex or edge (index 0 for graph properties)
+    ///
+    /// Returns `None` if the index is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gt_graph_wasm::Graph;
+    /// use gt_graph_wasm::graph_file::properties::PropertyValue;
+    ///
+    /// let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    /// let mut graph = Graph::try_from(data).unwrap();
+    ///
+    /// let property = graph.edge_property("value".to_string()).unwrap();
+    /// assert!(matches!(property.value(0), Some(PropertyValue::Int16(_))));
+    /// assert_eq!(property.value(254), None);
+    /// ```
+    pub fn value(&self, index: usize) -> Option<PropertyValue<'_>> {
+        let value = match self.data() {
+            PropertyData::Bool(v) => PropertyValue::Bool(*v.get(index)? != 0),
+            PropertyData::Int16(v) => PropertyValue::Int16(*v.get(index)?),
+            PropertyData::Int32(v) => PropertyValue::Int32(*v.get(index)?),
+            PropertyData::Int64(v) => PropertyValue::Int64(*v.get(index)?),
+            PropertyData::Double(v) => PropertyValue::Double(*v.get(index)?),
+            PropertyData::LongDouble(v) => PropertyValue::LongDouble(*v.values.get(index)?),
+            PropertyData::String(v) => PropertyValue::String(v.get(index)?),
+            PropertyData::VectorBool(v) => PropertyValue::VectorBool(v.get(index)?),
+            PropertyData::VectorInt16(v) => PropertyValue::VectorInt16(v.get(index)?),
+            PropertyData::VectorInt32(v) => PropertyValue::VectorInt32(v.get(index)?),
+            PropertyData::VectorInt64(v) => PropertyValue::VectorInt64(v.get(index)?),
+            PropertyData::VectorDouble(v) => PropertyValue::VectorDouble(v.get(index)?),
+            PropertyData::VectorLongDouble(v) => {
+                PropertyValue::VectorLongDouble(&v.get(index)?.values)
             }
-            PropertyData::VectorBool(v) => {
-                // Array buffer view
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Uint8Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
+            PropertyData::VectorString(v) => PropertyValue::VectorString(v.get(index)?),
+            PropertyData::PyObject(v) => PropertyValue::PyObject(v.get(index)?),
+        };
+        Some(value)
+    }
+
+    /// Iterate over all values
+    pub fn values(&self) -> impl Iterator<Item = PropertyValue<'_>> + '_ {
+        (0..self.len()).map(|index| self.value(index).unwrap())
+    }
+
+    /// Replace all values of the property
+    ///
+    /// The value type of the property is kept, so the values have to fit it
+    /// (see [`Element`]). There has to be one value per vertex or edge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number or the type of the values does not match
+    pub fn set<T: Element>(&mut self, values: Vec<T>) -> Result<(), String> {
+        if values.len() != self.len() {
+            return Err(format!(
+                "{:?} property '{}' needs {} values, got {}",
+                self.map_type,
+                self.name,
+                self.len(),
+                values.len()
+            ));
+        }
+        // The old values are not needed, so they are not decoded
+        let mut data = PropertyData::with_capacity(self.value_type(), 0);
+        if !data.replace(Box::new(values)) {
+            return Err(format!(
+                "{} values do not fit the {} property '{}'",
+                std::any::type_name::<T>(),
+                self.value_type().name(),
+                self.name
+            ));
+        }
+        self.data = OnceCell::from(data);
+        self.encoded = None;
+        Ok(())
+    }
+
+    /// Replace all values of the property with values from JavaScript
+    ///
+    /// For vertex and edge properties `values` is an array or typed array with
+    /// one value per vertex or edge, for graph properties it is the value itself.
+    /// Vector values are arrays or typed arrays as well, python objects are
+    /// byte arrays (pickled). Int64 values can be numbers or BigInts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number or the type of the values does not match
+    pub fn set_js(&mut self, values: &JsValue) -> Result<(), String> {
+        let rows = if self.map_type == PropertyMapType::Graph {
+            js_sys::Array::of1(values)
+        } else {
+            js_sys::Array::from(values)
+        };
+        let vector = |row: &JsValue| js_sys::Array::from(row);
+
+        match self.value_type() {
+            PropertyType::Bool => self.set(js_map(&rows, js_bool)?),
+            PropertyType::Int16 => self.set(js_map(&rows, js_int::<i16>)?),
+            PropertyType::Int32 => self.set(js_map(&rows, js_int::<i32>)?),
+            PropertyType::Int64 => self.set(js_map(&rows, js_int::<i64>)?),
+            PropertyType::Double | PropertyType::LongDouble => self.set(js_map(&rows, js_f64)?),
+            PropertyType::String => self.set(js_map(&rows, js_string)?),
+            PropertyType::VectorBool => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_bool))?)
+            }
+            PropertyType::VectorInt16 => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_int::<i16>))?)
+            }
+            PropertyType::VectorInt32 => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_int::<i32>))?)
+            }
+            PropertyType::VectorInt64 => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_int::<i64>))?)
+            }
+            PropertyType::VectorDouble | PropertyType::VectorLongDouble => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_f64))?)
+            }
+            PropertyType::VectorString => {
+                self.set(js_map(&rows, |row| js_map(&vector(row), js_string))?)
+            }
+            PropertyType::PyObject => self.set(js_map(&rows, |row| {
+                Ok(js_sys::Uint8Array::new(row).to_vec())
+            })?),
+        }
+    }
+
+    /// Insert a value for a new vertex or edge at `index`
+    ///
+    /// As in graph-tool, the value is zero, empty or `None` (python objects).
+    pub(crate) fn insert_default(&mut self, index: usize) {
+        match self.data_mut() {
+            PropertyData::PyObject(v) => v.insert(index, PY_NONE.to_vec()),
+            data => data.column().insert_default(index),
+        }
+    }
+
+    /// Remove the values of deleted vertices or edges,
+    /// `keep` tells for each row whether it is kept
+    pub(crate) fn retain_rows(&mut self, mut keep: impl FnMut(usize) -> bool) {
+        self.data_mut().column().retain_rows(&mut keep);
+    }
+
+    /// Reorder the values, row `i` becomes the old row `order[i]`
+    pub(crate) fn permute_rows(&mut self, order: &[u64]) {
+        self.data_mut().column().permute_rows(order);
+    }
+
+    /// Get a copy of the data as a JsValue
+    ///
+    /// Numeric values are returned as typed arrays, strings as arrays of
+    /// strings and vectors as arrays of typed arrays (or arrays of strings).
+    /// The copies are owned by JavaScript and stay valid when the graph
+    /// changes or the wasm memory grows.
+    pub fn data_copy(&self) -> JsValue {
+        fn rows<T>(values: &[T], row: impl Fn(&T) -> JsValue) -> JsValue {
+            values.iter().map(row).collect::<js_sys::Array>().into()
+        }
+        fn strings(values: &[String]) -> JsValue {
+            rows(values, |value| JsValue::from_str(value))
+        }
+
+        match self.data() {
+            PropertyData::Bool(v) => js_sys::Uint8Array::from(v.as_slice()).into(),
+            PropertyData::Int16(v) => js_sys::Int16Array::from(v.as_slice()).into(),
+            PropertyData::Int32(v) => js_sys::Int32Array::from(v.as_slice()).into(),
+            PropertyData::Int64(v) => js_sys::BigInt64Array::from(v.as_slice()).into(),
+            PropertyData::Double(v) => js_sys::Float64Array::from(v.as_slice()).into(),
+            PropertyData::LongDouble(v) => js_sys::Float64Array::from(v.values.as_slice()).into(),
+            PropertyData::String(v) => strings(v),
+            PropertyData::VectorBool(v) | PropertyData::PyObject(v) => {
+                rows(v, |row| js_sys::Uint8Array::from(row.as_slice()).into())
             }
             PropertyData::VectorInt16(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Int16Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
+                rows(v, |row| js_sys::Int16Array::from(row.as_slice()).into())
             }
             PropertyData::VectorInt32(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Int32Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
+                rows(v, |row| js_sys::Int32Array::from(row.as_slice()).into())
             }
             PropertyData::VectorInt64(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::BigInt64Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
+                rows(v, |row| js_sys::BigInt64Array::from(row.as_slice()).into())
             }
             PropertyData::VectorDouble(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Float64Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
-            }
-            PropertyData::VectorLongDouble(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Float64Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
-            }
-            PropertyData::VectorString(v) => {
-                let array = js_sys::Array::new();
-                for (_, value) in v.iter_mut().enumerate() {
-                    let array_view = js_sys::Array::new();
-                    for (j, value) in value.iter().enumerate() {
-                        array_view.set(j as u32, JsValue::from_str(value));
-                    }
-                    array.push(&array_view);
-                }
-                array.into()
-            }
-            PropertyData::PyObject(v) => {
-                let array = js_sys::Array::new();
-                for (i, value) in v.iter_mut().enumerate() {
-                    let array_view = unsafe {
-                        let array_view =
-                            js_sys::Uint8Array::view_mut_raw(value.as_mut_ptr(), value.len());
-                        array_view.into()
-                    };
-                    array.set(i as u32, array_view);
-                }
-                array.into()
+                rows(v, |row| js_sys::Float64Array::from(row.as_slice()).into())
             }
+            PropertyData::VectorLongDouble(v) => rows(v, |row| {
+                js_sys::Float64Array::from(row.values.as_slice()).into()
+            }),
+            PropertyData::VectorString(v) => rows(v, |row| strings(row)),
+        }
+    }
+
+    /// Pointer to the values and their number, for properties with
+    /// fixed width values that can be viewed from js without copying
+    /// (bool, int16_t, int32_t, int64_t and double)
+    pub(crate) fn raw_parts(&mut self) -> Option<(*mut u8, usize)> {
+        // Borrowed values are copied first, the file must not be changed
+        match self.data_mut() {
+            PropertyData::Bool(v) => Some((v.to_mut().as_mut_ptr(), v.len())),
+            PropertyData::Int16(v) => Some((v.to_mut().as_mut_ptr() as *mut u8, v.len())),
+            PropertyData::Int32(v) => Some((v.to_mut().as_mut_ptr() as *mut u8, v.len())),
+            PropertyData::Int64(v) => Some((v.to_mut().as_mut_ptr() as *mut u8, v.len())),
+            PropertyData::Double(v) => Some((v.to_mut().as_mut_ptr() as *mut u8, v.len())),
+            _ => None,
+        }
+    }
+
+    /// Get a copy of the raw 16 byte long double values as a JsValue
+    ///
+    /// Returns a Uint8Array (16 bytes per value, little endian x87
+    /// extended precision) for long double properties and an array
+    /// of Uint8Arrays for vector long double properties.
+    pub fn long_double_copy(&self) -> Result<JsValue, String> {
+        match self.data() {
+            PropertyData::LongDouble(v) => Ok(v.raw_bytes().into()),
+            PropertyData::VectorLongDouble(v) => Ok(v
+                .iter()
+                .map(|value| JsValue::from(value.raw_bytes()))
+                .collect::<js_sys::Array>()
+                .into()),
+            _ => Err(format!(
+                "Property '{}' is not a long double property",
+                self.name
+            )),
         }
     }
 }
@@ -438,3 +1293,339 @@ impl TryFrom<u8> for PropertyType {
         }
     }
 }
+
+impl From<PropertyType> for u8 {
+    fn from(value: PropertyType) -> Self {
+        match value {
+            PropertyType::Bool => 0x00,
+            PropertyType::Int16 => 0x01,
+            PropertyType::Int32 => 0x02,
+            PropertyType::Int64 => 0x03,
+            PropertyType::Double => 0x04,
+            PropertyType::LongDouble => 0x05,
+            PropertyType::String => 0x06,
+            PropertyType::VectorBool => 0x07,
+            PropertyType::VectorInt16 => 0x08,
+            PropertyType::VectorInt32 => 0x09,
+            PropertyType::VectorInt64 => 0x0a,
+            PropertyType::VectorDouble => 0x0b,
+            PropertyType::VectorLongDouble => 0x0c,
+            PropertyType::VectorString => 0x0d,
+            PropertyType::PyObject => 0x0e,
+        }
+    }
+}
+
+impl PropertyType {
+    /// Size of a value in the file if all values have the same size
+    pub(crate) fn width(&self) -> Option<u64> {
+        match self {
+            PropertyType::Bool => Some(1),
+            PropertyType::Int16 => Some(2),
+            PropertyType::Int32 => Some(4),
+            PropertyType::Int64 | PropertyType::Double => Some(8),
+            PropertyType::LongDouble => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Name of the type in graph-tool, e.g. `vector<double>`
+    pub fn name(&self) -> &'static str {
+        match self {
+            PropertyType::Bool => "bool",
+            PropertyType::Int16 => "int16_t",
+            PropertyType::Int32 => "int32_t",
+            PropertyType::Int64 => "int64_t",
+            PropertyType::Double => "double",
+            PropertyType::LongDouble => "long double",
+            PropertyType::String => "string",
+            PropertyType::VectorBool => "vector<bool>",
+            PropertyType::VectorInt16 => "vector<int16_t>",
+            PropertyType::VectorInt32 => "vector<int32_t>",
+            PropertyType::VectorInt64 => "vector<int64_t>",
+            PropertyType::VectorDouble => "vector<double>",
+            PropertyType::VectorLongDouble => "vector<long double>",
+            PropertyType::VectorString => "vector<string>",
+            PropertyType::PyObject => "python::object",
+        }
+    }
+}
+
+/// Parse a type name as used by graph-tool, including its aliases
+/// (`int`, `float`, `vector<float>`, `object`, ...)
+impl TryFrom<&str> for PropertyType {
+    type Error = String;
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let scalar = |name: &str| match name {
+            "bool" | "uint8_t" => Some(0x00),
+            "int16_t" | "short" => Some(0x01),
+            "int32_t" | "int" => Some(0x02),
+            "int64_t" | "long" => Some(0x03),
+            "double" | "float" => Some(0x04),
+            "long double" => Some(0x05),
+            "string" => Some(0x06),
+            _ => None,
+        };
+        let index = match name.strip_prefix("vector<") {
+            Some(inner) => inner
+                .strip_suffix('>')
+                .and_then(scalar)
+                .map(|index| index + 0x07),
+            None if name == "python::object" || name == "object" => Some(0x0e),
+            None => scalar(name),
+        };
+        index
+            .and_then(|index| PropertyType::try_from(index).ok())
+            .ok_or_else(|| format!("Unknown property type '{}'", name))
+    }
+}
+
+/** Rust types which can be stored in a property map,
+ * used to set and get the values of a property.
+ *
+ * Bools are stored as `u8` (as in the file), long doubles are set
+ * from `f64` values and python objects are pickled bytes (`Vec<u8>`).
+ *
+ *  - `u8`: bool
+ *  - `i16`, `i32`, `i64`: int16_t, int32_t, int64_t
+ *  - `f64`: double, long double (rounded when read)
+ *  - `String`: string
+ *  - `Vec<u8>`: vector<bool>, python::object (set only, read with `Property::as_objects`)
+ *  - `Vec<i16>`, `Vec<i32>`, `Vec<i64>`: vector<int16_t>, vector<int32_t>, vector<int64_t>
+ *  - `Vec<f64>`: vector<double>, vector<long double>
+ *  - `Vec<String>`: vector<string>
+ */
+pub trait Element: sealed::Sealed + 'static {
+    /// The value types which `Property::get` returns as this type
+    const VALUE_TYPES: &'static [PropertyType];
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! element {
+    ($($type:ty => [$($value_type:ident),+]),+ $(,)?) => {
+        $(
+            impl Element for $type {
+                const VALUE_TYPES: &'static [PropertyType] = &[$(PropertyType::$value_type),+];
+            }
+            impl sealed::Sealed for $type {}
+        )+
+    };
+}
+
+element!(
+    u8 => [Bool],
+    i16 => [Int16],
+    i32 => [Int32],
+    i64 => [Int64],
+    f64 => [Double, LongDouble],
+    String => [String],
+);
+element!(
+    Vec<u8> => [VectorBool],
+    Vec<i16> => [VectorInt16],
+    Vec<i32> => [VectorInt32],
+    Vec<i64> => [VectorInt64],
+    Vec<f64> => [VectorDouble],
+    Vec<String> => [VectorString],
+);
+
+/// Read `len` values of the given type
+fn read_values<R: Read, B: ByteOrder>(
+    reader: &mut Reader<R, B>,
+    value_type: PropertyType,
+    len: u64,
+) -> Result<PropertyData, GtError> {
+    // Initialize the property data array
+    let mut property_data = PropertyData::with_capacity(value_type, reader.capacity(len));
+
+    // Fill the array
+    for _ in 0..len {
+        match &mut property_data {
+            PropertyData::Bool(v) => {
+                // read the bool
+                v.to_mut().push(reader.read_u8()?);
+            }
+            PropertyData::Int16(v) => {
+                v.to_mut().push(reader.read_i16()?);
+            }
+            PropertyData::Int32(v) => {
+                v.to_mut().push(reader.read_i32()?);
+            }
+            PropertyData::Int64(v) => {
+                v.to_mut().push(reader.read_i64()?);
+            }
+            PropertyData::Double(v) => {
+                v.to_mut().push(reader.read_f64()?);
+            }
+            PropertyData::LongDouble(v) => {
+                v.push(reader.read_u128()?);
+            }
+            PropertyData::String(v) => {
+                v.push(reader.read_string()?);
+            }
+            PropertyData::VectorBool(v) => {
+                let vector_length = reader.read_u64()?;
+                v.push(reader.read_bytes(vector_length)?);
+            }
+            PropertyData::VectorInt16(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = Vec::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_i16()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::VectorInt32(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = Vec::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_i32()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::VectorInt64(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = Vec::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_i64()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::VectorDouble(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = Vec::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_f64()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::VectorLongDouble(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = LongDoubles::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_u128()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::VectorString(v) => {
+                let vector_length = reader.read_u64()?;
+                let mut vector = Vec::with_capacity(reader.capacity(vector_length));
+                for _ in 0..vector_length {
+                    vector.push(reader.read_string()?);
+                }
+                v.push(vector);
+            }
+            PropertyData::PyObject(v) => {
+                let object_length = reader.read_u64()?;
+                v.push(reader.read_bytes(object_length)?);
+            }
+        }
+    }
+    Ok(property_data)
+}
+
+/// Move `values` into `target` if they have the same type
+fn replace_with<T: 'static>(target: &mut Vec<T>, values: Box<dyn Any>) -> bool {
+    match values.downcast::<Vec<T>>() {
+        Ok(values) => {
+            *target = *values;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Convert every entry of a JavaScript array
+fn js_map<T>(
+    array: &js_sys::Array,
+    convert: impl Fn(&JsValue) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    array.iter().map(|value| convert(&value)).collect()
+}
+
+fn js_bool(value: &JsValue) -> Result<u8, String> {
+    match (value.as_bool(), value.as_f64()) {
+        (Some(value), _) => Ok(value as u8),
+        (_, Some(value)) => Ok((value != 0.0) as u8),
+        _ => Err(format!("{:?} is not a bool", value)),
+    }
+}
+
+/// Integers can be given as numbers (without fraction) or BigInts
+fn js_int<T: TryFrom<i64>>(value: &JsValue) -> Result<T, String> {
+    let integer = match value.as_f64() {
+        Some(number) if number.fract() == 0.0 && number.abs() < i64::MAX as f64 => {
+            Some(number as i64)
+        }
+        Some(_) => None,
+        None => i64::try_from(value.clone()).ok(),
+    };
+    integer
+        .and_then(|integer| T::try_from(integer).ok())
+        .ok_or_else(|| format!("{:?} is not a valid {}", value, std::any::type_name::<T>()))
+}
+
+fn js_f64(value: &JsValue) -> Result<f64, String> {
+    match value.as_f64() {
+        Some(number) => Ok(number),
+        None => i64::try_from(value.clone())
+            .map(|integer| integer as f64)
+            .map_err(|_| format!("{:?} is not a number", value)),
+    }
+}
+
+fn js_string(value: &JsValue) -> Result<String, String> {
+    value
+        .as_string()
+        .ok_or_else(|| format!("{:?} is not a string", value))
+}
+
+/// Skip `len` values, fixed width values are skipped at once
+fn skip_values<R: Read + Skip, B: ByteOrder>(
+    reader: &mut Reader<R, B>,
+    value_type: PropertyType,
+    len: u64,
+) -> Result<(), GtError> {
+    match value_type.width() {
+        Some(width) => reader.skip(len.saturating_mul(width)),
+        None => (0..len).try_for_each(|_| skip_value(reader, value_type)),
+    }
+}
+
+/// Skip a value which is prefixed by its length (strings, vectors and python objects)
+fn skip_value<R: Read + Skip, B: ByteOrder>(
+    reader: &mut Reader<R, B>,
+    value_type: PropertyType,
+) -> Result<(), GtError> {
+    let element_size = match value_type {
+        PropertyType::VectorInt16 => 2,
+        PropertyType::VectorInt32 => 4,
+        PropertyType::VectorInt64 | PropertyType::VectorDouble => 8,
+        PropertyType::VectorLongDouble => 16,
+        PropertyType::String => return reader.skip_string(),
+        PropertyType::VectorString => {
+            for _ in 0..reader.read_u64()? {
+                reader.skip_string()?;
+            }
+            return Ok(());
+        }
+        // vector<bool> and python objects take one byte per element
+        _ => 1,
+    };
+    let len = reader.read_u64()?;
+    reader.skip(len.saturating_mul(element_size))
+}
+
+/// Whether `B` is big endian
+fn is_big_endian<B: ByteOrder>() -> bool {
+    B::read_u16(&[0, 1]) == 1
+}
+
+/// Write a string prefixed by its length (u64)
+fn write_string<B: ByteOrder, W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    writer.write_u64::<B>(value.len() as u64)?;
+    writer.write_all(value.as_bytes())
+}
@@ -0,0 +1,263 @@
+use std::io::{self, Read};
+use std::marker::PhantomData;
+
+use byteorder::{ByteOrder, ReadBytesExt};
+
+use crate::error::{GtError, Section};
+
+/// Maximum number of elements reserved up front if the input length is unknown
+const STREAM_CAPACITY: u64 = 1 << 16;
+
+/** Inputs which can move forward without handing out the bytes.
+ * Slices simply move their start, other readers read and drop
+ * the bytes (see `Discard`).
+ */
+pub(crate) trait Skip {
+    fn skip(&mut self, len: u64) -> io::Result<()>;
+
+    /// Skip a string of `len` bytes, returns false if it is not valid UTF-8.
+    /// Only inputs which see the bytes without reading them check them.
+    fn skip_str(&mut self, len: u64) -> io::Result<bool> {
+        self.skip(len).map(|_| true)
+    }
+}
+
+impl Skip for &[u8] {
+    fn skip(&mut self, len: u64) -> io::Result<()> {
+        if len > self.len() as u64 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        *self = &self[len as usize..];
+        Ok(())
+    }
+
+    fn skip_str(&mut self, len: u64) -> io::Result<bool> {
+        if len > self.len() as u64 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let valid = std::str::from_utf8(&self[..len as usize]).is_ok();
+        self.skip(len)?;
+        Ok(valid)
+    }
+}
+
+/// A reader which skips by reading into a small buffer (e.g. a decompressor
+/// that can't seek), nothing is allocated for the skipped bytes
+pub(crate) struct Discard<R: Read>(pub R);
+
+impl<R: Read> Read for Discard<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: Read> Skip for Discard<R> {
+    fn skip(&mut self, len: u64) -> io::Result<()> {
+        let skipped = io::copy(&mut (&mut self.0).take(len), &mut io::sink())?;
+        if skipped < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+}
+
+/** Reader over the raw gt file which remembers the section
+ * that is currently parsed. Every failing read is reported as
+ * a `GtError` carrying the offset and the section.
+ *
+ * The input can be any `Read` (e.g. a streaming decompressor), the
+ * offset is tracked while reading. Multi-byte values are read in
+ * the byte order `B`.
+ */
+pub(crate) struct Reader<R: Read, B: ByteOrder> {
+    inner: R,
+    offset: u64,
+    /// Length of the input if it is known up front
+    len: Option<u64>,
+    pub section: Section,
+    byte_order: PhantomData<B>,
+}
+
+impl<'a, B: ByteOrder> Reader<&'a [u8], B> {
+    pub fn from_slice(data: &'a [u8]) -> Reader<&'a [u8], B> {
+        Reader {
+            len: Some(data.len() as u64),
+            ..Reader::new(data)
+        }
+    }
+}
+
+impl<R: Read, B: ByteOrder> Reader<R, B> {
+    pub fn new(inner: R) -> Reader<R, B> {
+        Reader {
+            inner,
+            offset: 0,
+            len: None,
+            section: Section::Header,
+            byte_order: PhantomData,
+        }
+    }
+
+    /// Continue reading at the same position with another byte order
+    pub fn with_byte_order<C: ByteOrder>(self) -> Reader<R, C> {
+        Reader {
+            inner: self.inner,
+            offset: self.offset,
+            len: self.len,
+            section: self.section,
+            byte_order: PhantomData,
+        }
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Number of bytes left in the input (if the length of the input is known)
+    pub fn remaining(&self) -> Option<u64> {
+        self.len.map(|len| len.saturating_sub(self.offset))
+    }
+
+    /// Capacity to reserve for `n` elements. Every element takes at least one
+    /// byte in the file, so corrupt lengths can't trigger huge allocations.
+    pub fn capacity(&self, n: u64) -> usize {
+        self.capacity_of(n, 1)
+    }
+
+    /// Capacity to reserve for `n` elements taking at least `size` bytes each.
+    ///
+    /// If the length of the input is unknown, at most `STREAM_CAPACITY`
+    /// elements are reserved and the vector grows while reading.
+    pub fn capacity_of(&self, n: u64, size: u64) -> usize {
+        match self.remaining() {
+            Some(remaining) => n.min(remaining / size) as usize,
+            None => n.min(STREAM_CAPACITY) as usize,
+        }
+    }
+
+    pub fn truncated(&self) -> GtError {
+        self.error_at(self.offset, io::ErrorKind::UnexpectedEof.into())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, GtError> {
+        let value = self.inner.read_u8();
+        self.advance(value, 1)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, GtError> {
+        let value = self.inner.read_u16::<B>();
+        self.advance(value, 2)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, GtError> {
+        let value = self.inner.read_u32::<B>();
+        self.advance(value, 4)
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, GtError> {
+        let value = self.inner.read_u64::<B>();
+        self.advance(value, 8)
+    }
+
+    pub fn read_u128(&mut self) -> Result<u128, GtError> {
+        let value = self.inner.read_u128::<B>();
+        self.advance(value, 16)
+    }
+
+    pub fn read_i16(&mut self) -> Result<i16, GtError> {
+        let value = self.inner.read_i16::<B>();
+        self.advance(value, 2)
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, GtError> {
+        let value = self.inner.read_i32::<B>();
+        self.advance(value, 4)
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, GtError> {
+        let value = self.inner.read_i64::<B>();
+        self.advance(value, 8)
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, GtError> {
+        let value = self.inner.read_f64::<B>();
+        self.advance(value, 8)
+    }
+
+    /// Read `len` raw bytes
+    pub fn read_bytes(&mut self, len: u64) -> Result<Vec<u8>, GtError> {
+        if self.remaining().is_some_and(|remaining| len > remaining) {
+            return Err(self.truncated());
+        }
+        let mut bytes = Vec::with_capacity(self.capacity(len));
+        let read = (&mut self.inner).take(len).read_to_end(&mut bytes);
+        let result = match read {
+            Ok(read) if read as u64 == len => Ok(bytes),
+            Ok(_) => Err(io::ErrorKind::UnexpectedEof.into()),
+            Err(e) => Err(e),
+        };
+        self.advance(result, len)
+    }
+
+    /// Read a string which is prefixed by its length (u64)
+    pub fn read_string(&mut self) -> Result<String, GtError> {
+        let len = self.read_u64()?;
+        let offset = self.offset();
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes).map_err(|_| GtError::InvalidUtf8 {
+            offset,
+            section: self.section.clone(),
+        })
+    }
+
+    /// Move the offset forward by `size` bytes if the read succeeded
+    fn advance<T>(&mut self, value: io::Result<T>, size: u64) -> Result<T, GtError> {
+        match value {
+            Ok(value) => {
+                self.offset += size;
+                Ok(value)
+            }
+            Err(e) => Err(self.error_at(self.offset, e)),
+        }
+    }
+
+    /// Errors of the underlying reader (e.g. corrupt compressed data) are
+    /// reported as `GtError::Decode`, a premature end as `GtError::Truncated`
+    fn error_at(&self, offset: u64, error: io::Error) -> GtError {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => GtError::Truncated {
+                offset,
+                section: self.section.clone(),
+            },
+            _ => GtError::Decode(error.to_string()),
+        }
+    }
+}
+
+impl<R: Read + Skip, B: ByteOrder> Reader<R, B> {
+    /// Move `len` bytes forward without reading them into memory
+    pub fn skip(&mut self, len: u64) -> Result<(), GtError> {
+        if self.remaining().is_some_and(|remaining| len > remaining) {
+            return Err(self.truncated());
+        }
+        let result = self.inner.skip(len);
+        self.advance(result, len)
+    }
+
+    /// Skip a string which is prefixed by its length (u64)
+    pub fn skip_string(&mut self) -> Result<(), GtError> {
+        let len = self.read_u64()?;
+        let offset = self.offset();
+        if self.remaining().is_some_and(|remaining| len > remaining) {
+            return Err(self.truncated());
+        }
+        let result = self.inner.skip_str(len);
+        match self.advance(result, len)? {
+            true => Ok(()),
+            false => Err(GtError::InvalidUtf8 {
+                offset,
+                section: self.section.clone(),
+            }),
+        }
+    }
+}
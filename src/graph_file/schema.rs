@@ -0,0 +1,34 @@
+use super::properties::{PropertyMapType, PropertyType};
+
+/// The fixed part at the start of a gt file, see `GraphFile::read_header`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphHeader {
+    pub version: u8,
+    /// 0x00 (little endian) or 0x01 (big endian)
+    pub endianness: u8,
+    pub comment: String,
+    pub directed: bool,
+    pub num_vertices: u64,
+}
+
+/// Summary of a gt file without its data, see `GraphFile::read_schema`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSchema {
+    pub header: GraphHeader,
+    pub num_edges: u64,
+    pub properties: Vec<PropertySchema>,
+}
+
+/// Name, types and location of a property in the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertySchema {
+    pub name: String,
+    pub map_type: PropertyMapType,
+    pub value_type: PropertyType,
+    /// Number of values
+    pub len: u64,
+    /// Position of the first value in the (decompressed) file
+    pub offset: u64,
+    /// Number of bytes taken by the values in the file
+    pub size: u64,
+}
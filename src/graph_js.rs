@@ -1,5 +1,25 @@
 use wasm_bindgen::prelude::*;
 
+/** Centrality measures stored as new
+ * vertex and edge properties.
+ */
+pub mod centrality;
+
+/** Clustering coefficients, triangles
+ * and k-cores as typed arrays.
+ */
+pub mod clustering;
+
+/** Connected components and the
+ * extraction of the largest one.
+ */
+pub mod components;
+
+/** Adding and removing vertices and edges
+ * from JavaScript.
+ */
+pub mod edit;
+
 /** Defines a number of input
  * and output methods for the graph.
  * To be used by javascript.
@@ -11,7 +31,25 @@ pub mod io;
  */
 pub mod properties;
 
+/** Weighted shortest paths with options
+ * given as plain objects.
+ */
+pub mod shortest_paths;
+
+/** Breadth-first and depth-first search
+ * returning typed arrays.
+ */
+pub mod traversal;
+
+/** Views into property values which survive
+ * the growth of the wasm memory.
+ */
+pub mod view;
+
 use crate::graph::Graph;
+use crate::graph_file::properties::PropertyMapType;
+use crate::graph_file::Neighbors;
+use view::PropertyView;
 
 /** The GraphJS struct represents a graph in JavaScript
  * and provides a number of methods to access the data.
@@ -21,6 +59,8 @@ use crate::graph::Graph;
 #[wasm_bindgen(js_name = Graph)]
 pub struct GraphJS {
     graph: Graph,
+    /// Invalidates property and adjacency views whenever the graph changes
+    generation: view::Generation,
 }
 
 /** JavaScript methods for the GraphJS struct */
@@ -61,21 +101,205 @@ impl GraphJS {
         self.graph.directed()
     }
 
+    /// Version of the gt format the graph was read from
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> u8 {
+        self.graph.version()
+    }
+
+    /// Byte order of the file, `"little"` or `"big"`
+    #[wasm_bindgen(getter)]
+    pub fn endianness(&self) -> String {
+        endianness_name(self.graph.endianness()).to_string()
+    }
+
+    /// Comment of the gt file, it is written back when the graph is saved
+    #[wasm_bindgen(getter)]
+    pub fn comment(&self) -> String {
+        self.graph.comment().to_string()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_comment(&mut self, comment: String) {
+        self.graph.set_comment(comment);
+    }
+
     pub fn out_neighbors(&self, node: u64) -> js_sys::BigUint64Array {
-        let ret = js_sys::BigUint64Array::from(self.graph.out_neighbors(node));
+        let ret = js_sys::BigUint64Array::from(self.graph.out_neighbors(node).to_vec().as_slice());
         ret
     }
 
+    /// Source and target of the edge with the given index, as a `BigUint64Array`
+    /// of length 2. Returns `undefined` if there is no such edge.
+    ///
+    /// Edge indices are the positions of the edge in edge properties.
+    pub fn edge(&self, index: u64) -> Option<js_sys::BigUint64Array> {
+        let (source, target) = self.graph.edge(index)?;
+        Some(js_sys::BigUint64Array::from(&[source, target][..]))
+    }
+
+    /// Indices of all edges between `source` and `target` (empty if there is none)
+    pub fn edge_index(&self, source: u64, target: u64) -> js_sys::BigUint64Array {
+        js_sys::BigUint64Array::from(self.graph.edge_index(source, target).as_slice())
+    }
+
+    /// Indices of the out edges of a node
+    pub fn out_edges(&self, node: u64) -> js_sys::BigUint64Array {
+        let edges: Vec<u64> = self.graph.out_edges(node).collect();
+        js_sys::BigUint64Array::from(edges.as_slice())
+    }
+
+    /// Indices of the in edges of a node
+    pub fn in_edges(&self, node: u64) -> js_sys::BigUint64Array {
+        let edges: Vec<u64> = self.graph.in_edges(node).collect();
+        js_sys::BigUint64Array::from(edges.as_slice())
+    }
+
+    /// Offsets of the out-neighbors of each node in `out_targets` (CSR layout).
+    ///
+    /// The out-neighbors of node `v` are `out_targets()[offsets[v]..offsets[v + 1]]`.
+    /// Every edge is stored once with its source, as in the file, also for
+    /// undirected graphs.
+    ///
+    /// The offsets are copied, so the array stays valid when the graph
+    /// changes or the wasm memory grows. See `out_offsets_view` to read them
+    /// without copying.
+    pub fn out_offsets(&self) -> js_sys::BigUint64Array {
+        js_sys::BigUint64Array::from(self.graph.adjacency().offsets())
+    }
+
+    /// A view of the offsets of `out_offsets` without copying them
+    ///
+    /// The view becomes invalid once the graph changes, see `PropertyView`.
+    /// Its arrays must not be written to.
+    pub fn out_offsets_view(&self) -> PropertyView {
+        PropertyView::offsets(self.graph.adjacency(), &self.generation)
+    }
+
+    /// Targets of all edges, ordered by source (CSR layout).
+    ///
+    /// Position `i` is the edge with index `i` unless edges were added to
+    /// the graph (see `add_edge`).
+    ///
+    /// Returns a `Uint32Array`, or a `BigUint64Array` if the graph has more
+    /// than 2^32 nodes. Like `out_offsets`, the targets are copied, see
+    /// `out_targets_view` to read them without copying.
+    pub fn out_targets(&self) -> JsValue {
+        match self.graph.adjacency().targets() {
+            Neighbors::U32(targets) => js_sys::Uint32Array::from(targets).into(),
+            Neighbors::U64(targets) => js_sys::BigUint64Array::from(targets).into(),
+        }
+    }
+
+    /// A view of the targets of `out_targets` without copying them
+    ///
+    /// Like `out_offsets_view`, the view becomes invalid once the graph
+    /// changes and its arrays must not be written to.
+    pub fn out_targets_view(&self) -> PropertyView {
+        PropertyView::targets(self.graph.adjacency(), &self.generation)
+    }
+
     pub fn in_neighbors(&self, node: u64) -> js_sys::BigUint64Array {
-        let ret = js_sys::BigUint64Array::from(self.graph.in_neighbors(node).as_slice());
+        let ret = js_sys::BigUint64Array::from(self.graph.in_neighbors(node).to_vec().as_slice());
         ret
     }
+
+    pub fn all_neighbors(&self, node: u64) -> js_sys::BigUint64Array {
+        let neighbors: Vec<u64> = self.graph.all_neighbors(node).collect();
+        js_sys::BigUint64Array::from(neighbors.as_slice())
+    }
+
+    pub fn out_degree(&self, node: u64) -> u64 {
+        self.graph.out_degree(node)
+    }
+
+    pub fn in_degree(&self, node: u64) -> u64 {
+        self.graph.in_degree(node)
+    }
 }
 
 impl Default for GraphJS {
     fn default() -> Self {
-        GraphJS {
-            graph: Graph::new(),
-        }
+        GraphJS::from(Graph::new())
+    }
+}
+
+impl GraphJS {
+    /// Mutable access to the graph, invalidates all property views
+    fn graph_mut(&mut self) -> &mut Graph {
+        self.generation.advance();
+        &mut self.graph
+    }
+}
+
+impl Drop for GraphJS {
+    fn drop(&mut self) {
+        self.generation.advance();
+    }
+}
+
+/// Build a plain JavaScript object from key value pairs
+pub(crate) fn plain_object(fields: Vec<(&str, JsValue)>) -> js_sys::Object {
+    let object = js_sys::Object::new();
+    for (key, value) in fields {
+        // Setting a property of a new plain object can not fail
+        js_sys::Reflect::set(&object, &key.into(), &value).unwrap();
+    }
+    object
+}
+
+/// Name of a map type as used in plain objects
+pub(crate) fn map_type_name(map_type: PropertyMapType) -> &'static str {
+    match map_type {
+        PropertyMapType::Graph => "graph",
+        PropertyMapType::Vertex => "vertex",
+        PropertyMapType::Edge => "edge",
+    }
+}
+
+/// Name of the byte order of a gt file
+pub(crate) fn endianness_name(endianness: u8) -> &'static str {
+    match endianness {
+        0x01 => "big",
+        _ => "little",
+    }
+}
+
+/// A string field of an options object, `None` if it is missing
+pub(crate) fn string_option(
+    options: &js_sys::Object,
+    name: &str,
+) -> Result<Option<String>, JsValue> {
+    let value = js_sys::Reflect::get(options, &name.into())?;
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    match value.as_string() {
+        Some(value) => Ok(Some(value)),
+        None => Err(format!("Option '{}' has to be a string, got {:?}", name, value).into()),
+    }
+}
+
+/// A number field of an options object, `None` if it is missing
+pub(crate) fn number_option(options: &js_sys::Object, name: &str) -> Result<Option<f64>, JsValue> {
+    let value = js_sys::Reflect::get(options, &name.into())?;
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    match value.as_f64() {
+        Some(value) => Ok(Some(value)),
+        None => Err(format!("Option '{}' has to be a number, got {:?}", name, value).into()),
+    }
+}
+
+/// A boolean field of an options object, `None` if it is missing
+pub(crate) fn bool_option(options: &js_sys::Object, name: &str) -> Result<Option<bool>, JsValue> {
+    let value = js_sys::Reflect::get(options, &name.into())?;
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    match value.as_bool() {
+        Some(value) => Ok(Some(value)),
+        None => Err(format!("Option '{}' has to be a boolean, got {:?}", name, value).into()),
     }
 }
@@ -0,0 +1,117 @@
+use wasm_bindgen::prelude::*;
+
+use super::{bool_option, number_option, string_option};
+use crate::centrality::{BetweennessOptions, ClosenessOptions, KatzOptions, PageRankOptions};
+use crate::GraphJS;
+
+/// Centrality measures, stored as new `double` properties with the given
+/// names (replacing existing ones). Weights are given as the name of a
+/// numeric edge property in the `weight` field of the options.
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// PageRank of every vertex
+    ///
+    /// `options` may contain `damping` (0.85 by default), `personalization`
+    /// (name of a numeric vertex property) and `weight`.
+    pub fn pagerank(
+        &mut self,
+        name: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<(), JsValue> {
+        let mut pagerank = PageRankOptions::default();
+        if let Some(options) = options {
+            pagerank.damping = number_option(&options, "damping")?.unwrap_or(pagerank.damping);
+            pagerank.personalization = string_option(&options, "personalization")?;
+            pagerank.weight = string_option(&options, "weight")?;
+        }
+        Ok(self.graph_mut().pagerank(&name, &pagerank)?)
+    }
+
+    /// Betweenness of every vertex and edge
+    ///
+    /// `options` may contain `weight` and `normalized` (true by default).
+    pub fn betweenness(
+        &mut self,
+        vertex_name: String,
+        edge_name: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<(), JsValue> {
+        let mut betweenness = BetweennessOptions::default();
+        if let Some(options) = options {
+            betweenness.weight = string_option(&options, "weight")?;
+            betweenness.normalized =
+                bool_option(&options, "normalized")?.unwrap_or(betweenness.normalized);
+        }
+        Ok(self
+            .graph_mut()
+            .betweenness(&vertex_name, &edge_name, &betweenness)?)
+    }
+
+    /// Closeness of every vertex, NaN for vertices which reach no other vertex
+    ///
+    /// `options` may contain `weight`, `harmonic` (false by default) and
+    /// `normalized` (true by default).
+    pub fn closeness(
+        &mut self,
+        name: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<(), JsValue> {
+        let mut closeness = ClosenessOptions::default();
+        if let Some(options) = options {
+            closeness.weight = string_option(&options, "weight")?;
+            closeness.harmonic = bool_option(&options, "harmonic")?.unwrap_or(closeness.harmonic);
+            closeness.normalized =
+                bool_option(&options, "normalized")?.unwrap_or(closeness.normalized);
+        }
+        Ok(self.graph_mut().closeness(&name, &closeness)?)
+    }
+
+    /// Eigenvector centrality of every vertex, returns the largest eigenvalue
+    ///
+    /// `options` may contain `weight`.
+    pub fn eigenvector(
+        &mut self,
+        name: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<f64, JsValue> {
+        let weight = match options {
+            Some(options) => string_option(&options, "weight")?,
+            None => None,
+        };
+        Ok(self.graph_mut().eigenvector(&name, weight.as_deref())?)
+    }
+
+    /// Katz centrality of every vertex
+    ///
+    /// `options` may contain `alpha` (0.01 by default), `beta` (name of a
+    /// numeric vertex property), `weight` and `normalized` (true by default).
+    pub fn katz(&mut self, name: String, options: Option<js_sys::Object>) -> Result<(), JsValue> {
+        let mut katz = KatzOptions::default();
+        if let Some(options) = options {
+            katz.alpha = number_option(&options, "alpha")?.unwrap_or(katz.alpha);
+            katz.beta = string_option(&options, "beta")?;
+            katz.weight = string_option(&options, "weight")?;
+            katz.normalized = bool_option(&options, "normalized")?.unwrap_or(katz.normalized);
+        }
+        Ok(self.graph_mut().katz(&name, &katz)?)
+    }
+
+    /// HITS authorities and hubs of every vertex, returns the largest
+    /// eigenvalue of the cocitation matrix
+    ///
+    /// `options` may contain `weight`.
+    pub fn hits(
+        &mut self,
+        authority_name: String,
+        hub_name: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<f64, JsValue> {
+        let weight = match options {
+            Some(options) => string_option(&options, "weight")?,
+            None => None,
+        };
+        Ok(self
+            .graph_mut()
+            .hits(&authority_name, &hub_name, weight.as_deref())?)
+    }
+}
@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+use super::plain_object;
+use crate::GraphJS;
+
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// The number of triangles of every vertex
+    pub fn triangles(&self) -> js_sys::BigUint64Array {
+        js_sys::BigUint64Array::from(self.graph.triangles().as_slice())
+    }
+
+    /// The local clustering coefficient of every vertex
+    pub fn local_clustering(&self) -> js_sys::Float64Array {
+        js_sys::Float64Array::from(self.graph.local_clustering().as_slice())
+    }
+
+    /// The global clustering coefficient as a plain object with the
+    /// `coefficient` and its jackknife standard deviation `error`
+    pub fn global_clustering(&self) -> js_sys::Object {
+        let (coefficient, error) = self.graph.global_clustering();
+        plain_object(vec![
+            ("coefficient", coefficient.into()),
+            ("error", error.into()),
+        ])
+    }
+
+    /// The core number of every vertex
+    pub fn kcore_decomposition(&self) -> js_sys::BigUint64Array {
+        js_sys::BigUint64Array::from(self.graph.kcore_decomposition().as_slice())
+    }
+}
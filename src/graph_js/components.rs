@@ -0,0 +1,61 @@
+use wasm_bindgen::prelude::*;
+
+use super::plain_object;
+use crate::components::Components;
+use crate::GraphJS;
+
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// Weakly connected components, edges connect their endpoints in both
+    /// directions
+    ///
+    /// Returns a plain object with two `BigUint64Array`s: `labels` (the
+    /// component of every vertex) and `sizes` (the number of vertices in
+    /// every component). Components are numbered in the order of their
+    /// smallest vertex.
+    pub fn weak_components(&self) -> js_sys::Object {
+        components_object(self.graph.weak_components())
+    }
+
+    /// Strongly connected components, see `weak_components` for the result
+    pub fn strong_components(&self) -> js_sys::Object {
+        components_object(self.graph.strong_components())
+    }
+
+    /// Mask of the vertices in the largest component (strong components in
+    /// directed graphs), one byte (0 or 1) per vertex
+    pub fn largest_component(&self) -> js_sys::Uint8Array {
+        mask_array(&self.graph.largest_component())
+    }
+
+    /// A new graph with the vertices of the largest component and all
+    /// properties
+    pub fn extract_largest_component(&self) -> GraphJS {
+        self.graph.extract_largest_component().into()
+    }
+
+    /// A new graph with the vertices whose entry in `mask` is not zero, the
+    /// edges between them and all properties
+    pub fn induced_subgraph(&self, mask: js_sys::Uint8Array) -> Result<GraphJS, JsValue> {
+        let keep: Vec<bool> = mask.to_vec().into_iter().map(|k| k != 0).collect();
+        Ok(self.graph.induced_subgraph(&keep)?.into())
+    }
+}
+
+fn components_object(components: Components) -> js_sys::Object {
+    plain_object(vec![
+        (
+            "labels",
+            js_sys::BigUint64Array::from(components.labels.as_slice()).into(),
+        ),
+        (
+            "sizes",
+            js_sys::BigUint64Array::from(components.sizes.as_slice()).into(),
+        ),
+    ])
+}
+
+fn mask_array(mask: &[bool]) -> js_sys::Uint8Array {
+    let bytes: Vec<u8> = mask.iter().map(|&k| k as u8).collect();
+    js_sys::Uint8Array::from(bytes.as_slice())
+}
@@ -0,0 +1,41 @@
+use wasm_bindgen::prelude::*;
+
+use crate::GraphJS;
+
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// Add a vertex without edges and return its index
+    ///
+    /// Vertex properties get the default value (zero or empty) for the new vertex.
+    pub fn add_vertex(&mut self) -> u64 {
+        self.graph_mut().add_vertex()
+    }
+
+    /// Add an edge from `source` to `target` and return its index
+    ///
+    /// The new edge gets the index `num_edges`, as in graph-tool, the indices
+    /// of all other edges stay the same. Edge properties get the default
+    /// value (zero or empty) for the new edge.
+    pub fn add_edge(&mut self, source: u64, target: u64) -> Result<u64, JsValue> {
+        Ok(self.graph_mut().add_edge(source, target)?)
+    }
+
+    /// Remove a vertex and all its edges
+    ///
+    /// All vertices with a higher index move down by one.
+    pub fn remove_vertex(&mut self, vertex: u64) -> Result<(), JsValue> {
+        Ok(self.graph_mut().remove_vertex(vertex)?)
+    }
+
+    /// Remove the edge with the given index
+    ///
+    /// All edges with a higher index move down by one.
+    pub fn remove_edge(&mut self, edge: u64) -> Result<(), JsValue> {
+        Ok(self.graph_mut().remove_edge(edge)?)
+    }
+
+    /// Remove all edges, the vertices are kept
+    pub fn clear_edges(&mut self) {
+        self.graph_mut().clear_edges()
+    }
+}
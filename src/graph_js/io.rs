@@ -1,31 +1,56 @@
 use wasm_bindgen::prelude::*;
 
+use super::{bool_option, endianness_name, map_type_name, plain_object};
+use crate::graph_file::schema::GraphHeader;
 use crate::graph_file::GraphFile;
-use crate::{Graph, GraphJS};
+use crate::io::ArrayReader;
+use crate::{Graph, GraphJS, GtError, LoadOptions};
 
 #[wasm_bindgen(js_class = Graph)]
 impl GraphJS {
     /// Create a graph from a URL.
     ///
-    /// The URL should point to a binary file in the gt format. It can be compressed with zsdt.
+    /// The URL should point to a binary file in the gt format. It can be compressed with zstd, gzip
+    /// or xz, or be packed in a zip archive.
+    ///
+    /// The chunks of the (compressed) file are kept as JavaScript arrays while
+    /// it is downloaded, parsing starts once the download is complete. zstd
+    /// and gzip files are then decompressed and parsed in one pass, copying
+    /// the chunks into the decoder one by one, so neither the compressed nor
+    /// the decompressed file is held in wasm memory as a whole.
     ///
     /// # Arguments
     ///
     /// * `url` - The URL of the binary file.
+    /// * `options` - Optional object with the fields `properties`, an array with
+    ///   the names of the properties to load (other properties are skipped),
+    ///   and `lazy`: if true, the decompressed file is kept and every property
+    ///   is decoded on its first access, which is faster if only some are used
+    ///   but keeps the file in memory as long as the graph exists (false by
+    ///   default, all properties are decoded while loading).
+    ///
+    /// # Errors
+    ///
+    /// Rejects with the fetch error, or with an `Error` named `GtError`
+    /// (with `kind`, `offset` and `section` fields) if the file could not be parsed.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use gt_graph_wasm::GraphJS;
+    /// # async fn run() {
     /// let url = "https://example.com/graph.gt";
-    /// let graph = GraphJS::from_url(url.to_string()).await;
+    /// let graph = GraphJS::from_url(url.to_string(), None).await;
+    /// # }
     /// ```
-    pub async fn from_url(url: String) -> Result<GraphJS, JsValue> {
-        let data = crate::io::fetch_binary(url).await?;
-        let data = crate::decode::decodebuffer(&data)?;
-        let graph_file: GraphFile = data.as_slice().try_into()?;
-        let graph: GraphJS = graph_file.into();
-        Ok(graph)
+    pub async fn from_url(
+        url: String,
+        options: Option<js_sys::Object>,
+    ) -> Result<GraphJS, JsValue> {
+        let options = load_options(options)?;
+        let reader = crate::io::fetch_binary(url).await?;
+        let graph = Graph::from_reader_with(reader, &options)?;
+        Ok(graph.into())
     }
 
     /// Create a graph from the netzschleuder repository.
@@ -36,18 +61,22 @@ impl GraphJS {
     ///
     /// * `network` - The name of the network.
     /// * `sub_network` - Optional sub-network name. If not provided, the network name will be used.
+    /// * `options` - Optional load options, see `from_url`.
     ///
     /// # Examples
     ///
-    /// ```
-    /// use gt_graph_wasm::Graph;
+    /// ```no_run
+    /// use gt_graph_wasm::GraphJS;
+    /// # async fn run() {
     /// let network = "karate";
     /// let sub_network = Some("karate_club".to_string());
-    /// let graph = GraphJS::from_netzschleuder(network.to_string(), sub_network).await;
+    /// let graph = GraphJS::from_netzschleuder(network.to_string(), sub_network, None).await;
+    /// # }
     /// ```
     pub async fn from_netzschleuder(
         network: String,
         sub_network: Option<String>,
+        options: Option<js_sys::Object>,
     ) -> Result<GraphJS, JsValue> {
         let sub_network = match sub_network {
             Some(sub_network) => sub_network,
@@ -58,7 +87,7 @@ impl GraphJS {
             "https://networks.skewed.de/net/{}/files/{}.gt.zst",
             network, sub_network
         );
-        GraphJS::from_url(url).await
+        GraphJS::from_url(url, options).await
     }
 
     /// Create a graph from a binary file directly by passing a javascript Uint8Array.
@@ -68,33 +97,120 @@ impl GraphJS {
     /// # Arguments
     ///
     /// * `data` - The binary data of the graph file.
+    /// * `zip_entry` - Optional name of the file to load if `data` is a zip archive.
+    ///   If not provided, the first `.gt` file in the archive is used.
+    /// * `options` - Optional load options, see `from_url`.
+    ///
+    /// # Errors
+    ///
+    /// Throws an `Error` named `GtError` (with `kind`, `offset` and `section` fields)
+    /// if the file could not be parsed.
     ///
     /// # Examples
     ///
-    /// ```
-    /// use gt_graph_wasm::graph::Graph;
+    /// ```no_run
+    /// use gt_graph_wasm::GraphJS;
     /// // Load data
-    /// let data = include_bytes!("../tests/data/advogato.gt.zst");
-    /// let graph = Graph::from_data(data).unwrap();
+    /// let data = include_bytes!("../../test_data/network.gt.zst");
+    /// let data = js_sys::Uint8Array::from(&data[..]);
+    /// let graph = GraphJS::from_data(data, None, None).unwrap();
     /// ```
-    pub fn from_data(data: js_sys::Uint8Array) -> Result<GraphJS, JsValue> {
+    pub fn from_data(
+        data: js_sys::Uint8Array,
+        zip_entry: Option<String>,
+        options: Option<js_sys::Object>,
+    ) -> Result<GraphJS, JsValue> {
+        let options = load_options(options)?;
         let data = data.to_vec();
-        let graph = Graph::try_from(data)?;
+        let graph = match zip_entry {
+            Some(name) => {
+                let data =
+                    crate::decode::decodebuffer_zip(&data, Some(&name)).map_err(GtError::Decode)?;
+                match options.lazy {
+                    true => GraphFile::from_buffer_with(data, &options)?.into(),
+                    false => GraphFile::from_reader_with(data.as_slice(), &options)?.into(),
+                }
+            }
+            None => Graph::from_bytes_with(data, &options)?,
+        };
         Ok(graph.into())
     }
+
+    /// Read only the header of a (compressed) gt file
+    ///
+    /// Only the first bytes of `data` are copied into wasm memory and
+    /// decompressed.
+    ///
+    /// Returns a plain object with the fields `version`, `endianness`
+    /// (`"little"` or `"big"`), `comment`, `directed` and `num_vertices`.
+    pub fn read_header(data: js_sys::Uint8Array) -> Result<js_sys::Object, JsValue> {
+        let header = Graph::read_header_from_reader(ArrayReader::new(data))?;
+        Ok(plain_object(header_fields(header)))
+    }
+
+    /// Read the header, the number of edges and the property schema of a
+    /// (compressed) gt file without decoding the graph
+    ///
+    /// `data` is copied into wasm memory in chunks and decompressed only up to
+    /// the end of the schema, the skipped data is dropped.
+    ///
+    /// Returns the fields of `read_header` plus `num_edges` and `properties`,
+    /// an array with an object per property holding its `name`, `map_type`
+    /// (`"graph"`, `"vertex"` or `"edge"`), `value_type` (type index of the gt
+    /// format), `type_name` (e.g. `"vector<double>"`), `length` and `size`
+    /// (bytes of the values in the decompressed file).
+    pub fn read_schema(data: js_sys::Uint8Array) -> Result<js_sys::Object, JsValue> {
+        let schema = Graph::read_schema_from_reader(ArrayReader::new(data))?;
+        let properties: js_sys::Array = schema
+            .properties
+            .into_iter()
+            .map(|property| {
+                plain_object(vec![
+                    ("name", property.name.into()),
+                    ("map_type", map_type_name(property.map_type).into()),
+                    ("value_type", u8::from(property.value_type).into()),
+                    ("type_name", property.value_type.name().into()),
+                    ("length", (property.len as f64).into()),
+                    ("size", (property.size as f64).into()),
+                ])
+            })
+            .collect();
+
+        let mut fields = header_fields(schema.header);
+        fields.push(("num_edges", (schema.num_edges as f64).into()));
+        fields.push(("properties", properties.into()));
+        Ok(plain_object(fields))
+    }
+
+    /// Serialize the graph into the gt binary format.
+    ///
+    /// The result can be loaded by graph-tool or `from_data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `zstd_level` - Optional zstd compression level. If not provided, the
+    ///   data is not compressed (use `0` for the default level).
+    pub fn to_uint8array(&self, zstd_level: Option<i32>) -> Result<js_sys::Uint8Array, JsValue> {
+        let data = match zstd_level {
+            Some(level) => self.graph.to_bytes_zstd(level)?,
+            None => self.graph.to_bytes(),
+        };
+        Ok(js_sys::Uint8Array::from(data.as_slice()))
+    }
 }
 
 impl From<Graph> for GraphJS {
     fn from(graph: Graph) -> Self {
-        GraphJS { graph }
+        GraphJS {
+            graph,
+            generation: Default::default(),
+        }
     }
 }
 
 impl From<GraphFile> for GraphJS {
     fn from(file: GraphFile) -> Self {
-        GraphJS {
-            graph: Graph::from(file),
-        }
+        Graph::from(file).into()
     }
 }
 
@@ -106,3 +222,34 @@ impl TryFrom<Vec<u8>> for GraphJS {
         Ok(graph.into())
     }
 }
+
+fn header_fields(header: GraphHeader) -> Vec<(&'static str, JsValue)> {
+    vec![
+        ("version", header.version.into()),
+        ("endianness", endianness_name(header.endianness).into()),
+        ("comment", header.comment.into()),
+        ("directed", header.directed.into()),
+        ("num_vertices", (header.num_vertices as f64).into()),
+    ]
+}
+
+/// Read the load options from a plain object (`{ properties: [..] }`)
+fn load_options(options: Option<js_sys::Object>) -> Result<LoadOptions, JsValue> {
+    let mut load_options = LoadOptions::default();
+    let Some(options) = options else {
+        return Ok(load_options);
+    };
+    let properties = js_sys::Reflect::get(&options, &"properties".into())?;
+    if !properties.is_undefined() && !properties.is_null() {
+        let names = js_sys::Array::from(&properties)
+            .iter()
+            .map(|name| {
+                name.as_string()
+                    .ok_or_else(|| format!("{:?} is not a property name", name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        load_options.properties = Some(names);
+    }
+    load_options.lazy = bool_option(&options, "lazy")?.unwrap_or(false);
+    Ok(load_options)
+}
@@ -1,8 +1,11 @@
 use wasm_bindgen::prelude::*;
 
+use super::view::PropertyView;
+use super::{map_type_name, plain_object};
+use crate::graph_file::properties::{Property, PropertyMapType, PropertyType};
 use crate::GraphJS;
 
-#[wasm_bindgen]
+#[wasm_bindgen(js_class = Graph)]
 // Traits are not supported in wasm-bindgen
 impl GraphJS {
     /// Get a list of all graph property names
@@ -38,33 +41,197 @@ impl GraphJS {
         ret
     }
 
-    /// Get a graph property by its name
+    /// Metadata of all properties
+    ///
+    /// Returns an array of plain objects with the fields `name`, `map_type`
+    /// (`"graph"`, `"vertex"` or `"edge"`), `value_type` (the type index of
+    /// the gt format), `type_name` (e.g. `"vector<double>"`), `length`,
+    /// `bytes` (approximate memory used by the values) and `loaded` (whether
+    /// the values are decoded, properties are decoded on first access).
+    pub fn property_info(&self) -> js_sys::Array {
+        self.graph
+            .property_info()
+            .into_iter()
+            .map(|info| {
+                plain_object(vec![
+                    ("name", info.name.into()),
+                    ("map_type", map_type_name(info.map_type).into()),
+                    ("value_type", u8::from(info.value_type).into()),
+                    ("type_name", info.value_type.name().into()),
+                    ("length", (info.len as f64).into()),
+                    ("bytes", (info.bytes as f64).into()),
+                    ("loaded", info.loaded.into()),
+                ])
+            })
+            .collect()
+    }
+
+    /// Get a copy of a graph property by its name
     #[wasm_bindgen(js_name = get_graph_property)]
-    pub fn graph_properties(&mut self, name: String) -> Result<JsValue, JsValue> {
-        let property = self.graph.graph_property(name);
-        match property {
-            Ok(property) => Ok(property.data_view()),
-            Err(err) => Err(JsValue::from_str(&err)),
-        }
+    pub fn graph_properties(&self, name: String) -> Result<JsValue, JsValue> {
+        self.property_copy(&name, PropertyMapType::Graph)
     }
 
-    /// Get a vertex property by its name
+    /// Get a copy of a vertex property by its name
+    ///
+    /// The values are copied, see `get_vertex_property_view`
+    /// to access large numeric properties without copying.
     #[wasm_bindgen(js_name = get_vertex_property)]
-    pub fn vertex_properties(&mut self, name: String) -> Result<JsValue, JsValue> {
-        let property = self.graph.vertex_property(name);
-        match property {
-            Ok(property) => Ok(property.data_view()),
-            Err(err) => Err(JsValue::from_str(&err)),
-        }
+    pub fn vertex_properties(&self, name: String) -> Result<JsValue, JsValue> {
+        self.property_copy(&name, PropertyMapType::Vertex)
     }
 
-    /// Get an edge property by its name
+    /// Get a copy of an edge property by its name
     #[wasm_bindgen(js_name = get_edge_property)]
-    pub fn edge_properties(&mut self, name: String) -> Result<JsValue, JsValue> {
-        let property = self.graph.edge_property(name);
-        match property {
-            Ok(property) => Ok(property.data_view()),
-            Err(err) => Err(JsValue::from_str(&err)),
+    pub fn edge_properties(&self, name: String) -> Result<JsValue, JsValue> {
+        self.property_copy(&name, PropertyMapType::Edge)
+    }
+
+    /// Get a view into a vertex property without copying the values
+    ///
+    /// Only bool, int16_t, int32_t, int64_t and double properties can be
+    /// viewed. The view becomes invalid once the graph changes, see
+    /// `PropertyView` for details.
+    #[wasm_bindgen(js_name = get_vertex_property_view)]
+    pub fn vertex_property_view(&mut self, name: String) -> Result<PropertyView, JsValue> {
+        let property = self.graph.vertex_property(name)?;
+        Ok(PropertyView::new(property, &self.generation)?)
+    }
+
+    /// Get a view into an edge property without copying the values
+    #[wasm_bindgen(js_name = get_edge_property_view)]
+    pub fn edge_property_view(&mut self, name: String) -> Result<PropertyView, JsValue> {
+        let property = self.graph.edge_property(name)?;
+        Ok(PropertyView::new(property, &self.generation)?)
+    }
+
+    /// Get a copy of the raw bytes of a long double graph property by its name
+    ///
+    /// Returns the full precision x87 extended values (16 bytes each, little endian)
+    /// for callers that can not live with the rounding to f64.
+    #[wasm_bindgen(js_name = get_graph_property_raw)]
+    pub fn graph_properties_raw(&self, name: String) -> Result<JsValue, JsValue> {
+        let property = self
+            .graph
+            .property_ref(&name, Some(PropertyMapType::Graph))?;
+        Ok(property.long_double_copy()?)
+    }
+
+    /// Get a copy of the raw bytes of a long double vertex property by its name
+    #[wasm_bindgen(js_name = get_vertex_property_raw)]
+    pub fn vertex_properties_raw(&self, name: String) -> Result<JsValue, JsValue> {
+        let property = self
+            .graph
+            .property_ref(&name, Some(PropertyMapType::Vertex))?;
+        Ok(property.long_double_copy()?)
+    }
+
+    /// Get a copy of the raw bytes of a long double edge property by its name
+    #[wasm_bindgen(js_name = get_edge_property_raw)]
+    pub fn edge_properties_raw(&self, name: String) -> Result<JsValue, JsValue> {
+        let property = self
+            .graph
+            .property_ref(&name, Some(PropertyMapType::Edge))?;
+        Ok(property.long_double_copy()?)
+    }
+
+    /// Create a graph property, replacing one with the same name
+    ///
+    /// `value_type` is a graph-tool type name (e.g. `"double"`, `"string"` or
+    /// `"vector<int32_t>"`), `value` is the optional initial value.
+    pub fn new_graph_property(
+        &mut self,
+        name: String,
+        value_type: String,
+        value: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        self.new_property(&name, PropertyMapType::Graph, &value_type, value)
+    }
+
+    /// Create a vertex property, replacing one with the same name
+    ///
+    /// `values` is an optional array or typed array with one value per vertex,
+    /// without it every vertex gets the default value (zero or empty).
+    pub fn new_vertex_property(
+        &mut self,
+        name: String,
+        value_type: String,
+        values: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        self.new_property(&name, PropertyMapType::Vertex, &value_type, values)
+    }
+
+    /// Create an edge property, replacing one with the same name
+    ///
+    /// `values` is an optional array or typed array with one value per edge.
+    pub fn new_edge_property(
+        &mut self,
+        name: String,
+        value_type: String,
+        values: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        self.new_property(&name, PropertyMapType::Edge, &value_type, values)
+    }
+
+    /// Set the value of a graph property
+    pub fn set_graph_property(&mut self, name: String, value: JsValue) -> Result<(), JsValue> {
+        let property = self.graph_mut().graph_property(name)?;
+        Ok(property.set_js(&value)?)
+    }
+
+    /// Set all values of a vertex property from an array or typed array
+    pub fn set_vertex_property(&mut self, name: String, values: JsValue) -> Result<(), JsValue> {
+        let property = self.graph_mut().vertex_property(name)?;
+        Ok(property.set_js(&values)?)
+    }
+
+    /// Set all values of an edge property from an array or typed array
+    pub fn set_edge_property(&mut self, name: String, values: JsValue) -> Result<(), JsValue> {
+        let property = self.graph_mut().edge_property(name)?;
+        Ok(property.set_js(&values)?)
+    }
+
+    /// Delete a graph property
+    pub fn remove_graph_property(&mut self, name: String) -> Result<(), JsValue> {
+        self.graph_mut().remove_graph_property(&name)?;
+        Ok(())
+    }
+
+    /// Delete a vertex property
+    pub fn remove_vertex_property(&mut self, name: String) -> Result<(), JsValue> {
+        self.graph_mut().remove_vertex_property(&name)?;
+        Ok(())
+    }
+
+    /// Delete an edge property
+    pub fn remove_edge_property(&mut self, name: String) -> Result<(), JsValue> {
+        self.graph_mut().remove_edge_property(&name)?;
+        Ok(())
+    }
+}
+
+impl GraphJS {
+    fn property_copy(&self, name: &str, map_type: PropertyMapType) -> Result<JsValue, JsValue> {
+        let property = self.graph.property_ref(name, Some(map_type))?;
+        Ok(property.data_copy())
+    }
+
+    /// The property is only added if the values fit,
+    /// so a failing call leaves the graph unchanged
+    fn new_property(
+        &mut self,
+        name: &str,
+        map_type: PropertyMapType,
+        value_type: &str,
+        values: Option<JsValue>,
+    ) -> Result<(), JsValue> {
+        let value_type = PropertyType::try_from(value_type)?;
+        let len = self.graph.property_len(map_type);
+        let mut property = Property::new(name, map_type, value_type, len);
+        if let Some(values) = values {
+            property.set_js(&values)?;
         }
+        self.graph_mut().insert_property(property);
+        Ok(())
     }
 }
@@ -0,0 +1,82 @@
+use wasm_bindgen::prelude::*;
+
+use super::{bool_option, plain_object, string_option};
+use crate::shortest_paths::PathOptions;
+use crate::GraphJS;
+
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// Shortest distances from `source` to all vertices
+    ///
+    /// `options` is an optional object with the fields `weight` (name of
+    /// a numeric edge property, every edge has the weight 1 if it is not
+    /// given) and `negative_weights` (use Bellman-Ford instead of Dijkstra).
+    ///
+    /// Returns a plain object with `distances` (`Float64Array`, `Infinity`
+    /// for unreached vertices) and `predecessors` (`BigUint64Array`, the
+    /// source and unreached vertices are their own predecessor).
+    pub fn shortest_distance(
+        &self,
+        source: u64,
+        options: Option<js_sys::Object>,
+    ) -> Result<js_sys::Object, JsValue> {
+        let paths = self
+            .graph
+            .shortest_distances(source, &path_options(options)?)?;
+        Ok(plain_object(vec![
+            (
+                "distances",
+                js_sys::Float64Array::from(paths.distances.as_slice()).into(),
+            ),
+            (
+                "predecessors",
+                js_sys::BigUint64Array::from(paths.predecessors.as_slice()).into(),
+            ),
+        ]))
+    }
+
+    /// Shortest path from `source` to `target`
+    ///
+    /// Takes the options of `shortest_distance` and additionally `position`,
+    /// the name of a `vector<double>` vertex property to search with A*.
+    ///
+    /// Returns `undefined` if the target can't be reached, otherwise a plain
+    /// object with `vertices` and `edges` (`BigUint64Array`s along the path)
+    /// and the total `distance`.
+    pub fn shortest_path(
+        &self,
+        source: u64,
+        target: u64,
+        options: Option<js_sys::Object>,
+    ) -> Result<JsValue, JsValue> {
+        let path = self
+            .graph
+            .shortest_path(source, target, &path_options(options)?)?;
+        let Some(path) = path else {
+            return Ok(JsValue::UNDEFINED);
+        };
+        Ok(plain_object(vec![
+            (
+                "vertices",
+                js_sys::BigUint64Array::from(path.vertices.as_slice()).into(),
+            ),
+            (
+                "edges",
+                js_sys::BigUint64Array::from(path.edges.as_slice()).into(),
+            ),
+            ("distance", path.distance.into()),
+        ])
+        .into())
+    }
+}
+
+fn path_options(options: Option<js_sys::Object>) -> Result<PathOptions, JsValue> {
+    let mut path_options = PathOptions::default();
+    let Some(options) = options else {
+        return Ok(path_options);
+    };
+    path_options.weight = string_option(&options, "weight")?;
+    path_options.position = string_option(&options, "position")?;
+    path_options.negative_weights = bool_option(&options, "negative_weights")?.unwrap_or(false);
+    Ok(path_options)
+}
@@ -0,0 +1,43 @@
+use wasm_bindgen::prelude::*;
+
+use super::plain_object;
+use crate::traversal::Search;
+use crate::GraphJS;
+
+#[wasm_bindgen(js_class = Graph)]
+impl GraphJS {
+    /// Breadth-first search from `source`
+    ///
+    /// Returns a plain object with three `BigUint64Array`s: `order` (the
+    /// reached vertices in the order they were discovered), `predecessors`
+    /// and `distances` (one entry per vertex). The predecessor of the source
+    /// and of unreached vertices is the vertex itself, unreached vertices
+    /// have the distance 2^64 - 1.
+    pub fn bfs(&self, source: u64) -> Result<js_sys::Object, JsValue> {
+        Ok(search_object(self.graph.bfs(source)?))
+    }
+
+    /// Depth-first search from `source`, see `bfs` for the result
+    ///
+    /// The distances are the depths in the search tree.
+    pub fn dfs(&self, source: u64) -> Result<js_sys::Object, JsValue> {
+        Ok(search_object(self.graph.dfs(source)?))
+    }
+}
+
+fn search_object(search: Search) -> js_sys::Object {
+    plain_object(vec![
+        (
+            "order",
+            js_sys::BigUint64Array::from(search.order.as_slice()).into(),
+        ),
+        (
+            "predecessors",
+            js_sys::BigUint64Array::from(search.predecessors.as_slice()).into(),
+        ),
+        (
+            "distances",
+            js_sys::BigUint64Array::from(search.distances.as_slice()).into(),
+        ),
+    ])
+}
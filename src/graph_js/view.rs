@@ -0,0 +1,189 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::graph_file::properties::{Property, PropertyType};
+use crate::graph_file::{Adjacency, Neighbors};
+
+/// Counter shared by a graph and its property views. It is increased
+/// whenever the property data of the graph may move (the graph changes
+/// or is freed), which invalidates all views created before.
+#[derive(Clone, Default)]
+pub(crate) struct Generation(Rc<Cell<u64>>);
+
+impl Generation {
+    fn current(&self) -> u64 {
+        self.0.get()
+    }
+
+    pub(crate) fn advance(&self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+/** A view into the values of a property without copying them.
+ *
+ * `array` returns a typed array which points into the wasm memory.
+ * Typed arrays over the wasm memory are detached as soon as the memory
+ * grows (e.g. when another graph is loaded), so don't keep the array but
+ * get it from the view again: every call creates a fresh array over the
+ * current memory. Writing to the array changes the property.
+ *
+ * The view stays valid until the graph changes (vertices, edges or
+ * properties are added, set or removed) or is freed. After that `valid`
+ * is false and `array` throws.
+ *
+ * Views of the adjacency (see `out_offsets_view`) work the same way, but
+ * their arrays must not be written to.
+ */
+#[wasm_bindgen]
+pub struct PropertyView {
+    ptr: *mut u8,
+    len: usize,
+    array_type: ArrayType,
+    type_name: &'static str,
+    created: u64,
+    generation: Generation,
+}
+
+/// The typed array created by a view
+#[derive(Clone, Copy)]
+enum ArrayType {
+    Uint8,
+    Int16,
+    Int32,
+    BigInt64,
+    Float64,
+    Uint32,
+    BigUint64,
+}
+
+impl PropertyView {
+    /// Views are supported for bool, int16_t, int32_t, int64_t and double properties
+    pub(crate) fn new(property: &mut Property, generation: &Generation) -> Result<Self, String> {
+        let value_type = property.value_type();
+        let array_type = match value_type {
+            PropertyType::Bool => ArrayType::Uint8,
+            PropertyType::Int16 => ArrayType::Int16,
+            PropertyType::Int32 => ArrayType::Int32,
+            PropertyType::Int64 => ArrayType::BigInt64,
+            PropertyType::Double => ArrayType::Float64,
+            _ => {
+                return Err(format!(
+                "Property '{}' has values of type {} which can not be viewed, copy them instead",
+                property.name,
+                value_type.name()
+            ))
+            }
+        };
+        // Fixed width values always have raw parts
+        let (ptr, len) = property.raw_parts().unwrap();
+        Ok(PropertyView::from_raw(
+            ptr,
+            len,
+            array_type,
+            value_type.name(),
+            generation,
+        ))
+    }
+
+    /// View of the CSR offsets of the adjacency (`uint64_t`)
+    pub(crate) fn offsets(adjacency: &Adjacency, generation: &Generation) -> Self {
+        let offsets = adjacency.offsets();
+        PropertyView::from_raw(
+            offsets.as_ptr() as *mut u8,
+            offsets.len(),
+            ArrayType::BigUint64,
+            "uint64_t",
+            generation,
+        )
+    }
+
+    /// View of the CSR targets of the adjacency (`uint32_t` or `uint64_t`)
+    pub(crate) fn targets(adjacency: &Adjacency, generation: &Generation) -> Self {
+        let (ptr, len, array_type, type_name) = match adjacency.targets() {
+            Neighbors::U32(targets) => (
+                targets.as_ptr() as *mut u8,
+                targets.len(),
+                ArrayType::Uint32,
+                "uint32_t",
+            ),
+            Neighbors::U64(targets) => (
+                targets.as_ptr() as *mut u8,
+                targets.len(),
+                ArrayType::BigUint64,
+                "uint64_t",
+            ),
+        };
+        PropertyView::from_raw(ptr, len, array_type, type_name, generation)
+    }
+
+    fn from_raw(
+        ptr: *mut u8,
+        len: usize,
+        array_type: ArrayType,
+        type_name: &'static str,
+        generation: &Generation,
+    ) -> Self {
+        PropertyView {
+            ptr,
+            len,
+            array_type,
+            type_name,
+            created: generation.current(),
+            generation: generation.clone(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl PropertyView {
+    /// Whether the viewed property still exists unchanged
+    #[wasm_bindgen(getter)]
+    pub fn valid(&self) -> bool {
+        self.generation.current() == self.created
+    }
+
+    /// Number of values
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.len
+    }
+
+    /// Type of the values (graph-tool type name, `uint32_t` or `uint64_t`
+    /// for the adjacency)
+    #[wasm_bindgen(getter)]
+    pub fn value_type(&self) -> String {
+        self.type_name.to_string()
+    }
+
+    /// A typed array over the current wasm memory
+    ///
+    /// Throws if the graph changed since the view was created.
+    #[wasm_bindgen(getter)]
+    pub fn array(&self) -> Result<JsValue, JsValue> {
+        if !self.valid() {
+            return Err("The property view is no longer valid, the graph has changed".into());
+        }
+        let (ptr, len) = (self.ptr, self.len);
+        let array = unsafe {
+            match self.array_type {
+                ArrayType::Uint8 => js_sys::Uint8Array::view_mut_raw(ptr, len).into(),
+                ArrayType::Int16 => js_sys::Int16Array::view_mut_raw(ptr as *mut i16, len).into(),
+                ArrayType::Int32 => js_sys::Int32Array::view_mut_raw(ptr as *mut i32, len).into(),
+                ArrayType::BigInt64 => {
+                    js_sys::BigInt64Array::view_mut_raw(ptr as *mut i64, len).into()
+                }
+                ArrayType::Float64 => {
+                    js_sys::Float64Array::view_mut_raw(ptr as *mut f64, len).into()
+                }
+                ArrayType::Uint32 => js_sys::Uint32Array::view_mut_raw(ptr as *mut u32, len).into(),
+                ArrayType::BigUint64 => {
+                    js_sys::BigUint64Array::view_mut_raw(ptr as *mut u64, len).into()
+                }
+            }
+        };
+        Ok(array)
+    }
+}
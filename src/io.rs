@@ -1,7 +1,12 @@
+use std::collections::VecDeque;
+use std::io::Read;
+
 use js_sys::Promise;
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response};
+use web_sys::{
+    ReadableStream, ReadableStreamDefaultReader, Request, RequestInit, RequestMode, Response,
+};
 
 #[wasm_bindgen]
 extern "C" {
@@ -9,13 +14,19 @@ extern "C" {
     fn fetch_with_request_and_init(input: &Request, init: &RequestInit) -> Promise;
 }
 
-/** Create a fetch request to download the binary file and parse it
- * as a graph.
+/** Download a (compressed) binary file with a fetch request.
+ *
+ * The chunks of the response body stay JavaScript arrays, they are not
+ * copied into the wasm memory. The returned reader copies one chunk after
+ * the other into the decoder and drops each chunk once it is read, so
+ * the file is decompressed and parsed in one pass while the compressed
+ * file never takes wasm memory. The download is complete before the
+ * reader is returned: a `Read` can't wait for the next chunk.
  */
-pub async fn fetch_binary(url: String) -> Result<Vec<u8>, JsValue> {
-    let mut ops = RequestInit::new();
-    ops.method("GET");
-    ops.mode(RequestMode::Cors);
+pub async fn fetch_binary(url: String) -> Result<ArrayReader, JsValue> {
+    let ops = RequestInit::new();
+    ops.set_method("GET");
+    ops.set_mode(RequestMode::Cors);
 
     let request = Request::new_with_str_and_init(&url, &ops)?;
     // send headers and request binary
@@ -30,13 +41,69 @@ pub async fn fetch_binary(url: String) -> Result<Vec<u8>, JsValue> {
     assert!(resp_value.is_instance_of::<Response>());
     let resp: Response = resp_value.dyn_into()?;
 
-    // get binary data
-    let data_ret = JsFuture::from(resp.array_buffer()?).await?;
-    // `data` is an `ArrayBuffer`. Convert it to a `Uint8Array`, because that's
-    // what we need to work with for now.
-    let data = js_sys::Uint8Array::new(&data_ret);
-    // Convert this `Uint8Array` into a rust `Vec<u8>`.
-    let data = data.to_vec();
+    match resp.body() {
+        Some(body) => read_stream(&body).await,
+        None => Ok(ArrayReader::default()),
+    }
+}
+
+/// Collect the `Uint8Array` chunks of a `ReadableStream` without copying them
+async fn read_stream(stream: &ReadableStream) -> Result<ArrayReader, JsValue> {
+    let reader: ReadableStreamDefaultReader = stream.get_reader().dyn_into()?;
+    let mut chunks = ArrayReader::default();
+
+    loop {
+        let result = JsFuture::from(reader.read()).await?;
+        let done = js_sys::Reflect::get(&result, &"done".into())?;
+        if done.is_truthy() {
+            break;
+        }
+        let chunk: js_sys::Uint8Array =
+            js_sys::Reflect::get(&result, &"value".into())?.dyn_into()?;
+        chunks.push(chunk);
+    }
+
+    Ok(chunks)
+}
+
+/** Reads JavaScript `Uint8Array`s one after the other.
+ *
+ * Only the requested bytes are copied into the wasm memory, an array is
+ * dropped as soon as it is read to the end.
+ */
+#[derive(Default)]
+pub(crate) struct ArrayReader {
+    chunks: VecDeque<js_sys::Uint8Array>,
+    /// Position in the first chunk
+    offset: u32,
+}
+
+impl ArrayReader {
+    pub(crate) fn new(array: js_sys::Uint8Array) -> ArrayReader {
+        let mut reader = ArrayReader::default();
+        reader.push(array);
+        reader
+    }
+
+    fn push(&mut self, chunk: js_sys::Uint8Array) {
+        self.chunks.push_back(chunk);
+    }
+}
 
-    Ok(data)
+impl Read for ArrayReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while let Some(chunk) = self.chunks.front() {
+            if self.offset < chunk.length() {
+                let len = u32::try_from(buf.len()).unwrap_or(u32::MAX);
+                let end = self.offset.saturating_add(len).min(chunk.length());
+                let read = (end - self.offset) as usize;
+                chunk.subarray(self.offset, end).copy_to(&mut buf[..read]);
+                self.offset = end;
+                return Ok(read);
+            }
+            self.chunks.pop_front();
+            self.offset = 0;
+        }
+        Ok(0)
+    }
 }
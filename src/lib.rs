@@ -8,19 +8,28 @@ mod utils;
  * from JavaScript.
  */
 mod graph;
-pub use graph::Graph;
+pub use graph::{centrality, clustering, components, shortest_paths, traversal, Graph};
 mod graph_js;
-pub use graph_js::GraphJS;
+pub use graph_js::{view::PropertyView, GraphJS};
 
 /** Decoding for compress data
  *
- * at the moment only zstd is supported
+ * supports zstd, gzip, xz and zip archives
  */
 pub mod decode;
 
+/** Compression of serialized graphs (zstd)
+ */
+pub mod encode;
+
+/** Errors that can occur while loading a graph
+ */
+pub mod error;
+pub use error::GtError;
+
 pub mod graph_file;
 
-pub use graph_file::GraphFile;
+pub use graph_file::{GraphFile, LoadOptions};
 mod io;
 
 use utils::set_panic_hook;
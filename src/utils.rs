@@ -9,5 +9,11 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 macro_rules! console_log {
-    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
+    ($($t:tt)*) => {{
+        // Imported js functions panic outside of wasm
+        #[cfg(target_arch = "wasm32")]
+        $crate::log(&format_args!($($t)*).to_string());
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = format_args!($($t)*);
+    }};
 }
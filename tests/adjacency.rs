@@ -0,0 +1,174 @@
+use gt_graph_wasm::decode::decodebuffer;
+use gt_graph_wasm::graph_file::{Adjacency, Neighbors};
+use gt_graph_wasm::{Graph, GraphFile};
+
+fn network() -> Graph {
+    Graph::try_from(include_bytes!("../test_data/network.gt.zst").to_vec()).unwrap()
+}
+
+#[test]
+fn test_csr_layout() {
+    let graph = network();
+    let adjacency = graph.adjacency();
+    let offsets = adjacency.offsets();
+
+    assert_eq!(offsets.len(), 78);
+    assert_eq!(offsets[0], 0);
+    assert_eq!(offsets[77], 254);
+    assert!(offsets.windows(2).all(|w| w[0] <= w[1]));
+
+    // 77 vertices fit into u32 targets
+    assert!(matches!(adjacency.targets(), Neighbors::U32(_)));
+
+    // Edges are stored in CSR order
+    let (from, to) = graph.edges();
+    assert_eq!(adjacency.targets(), *to.as_slice());
+    for v in 0..graph.num_vertices() {
+        let start = offsets[v as usize] as usize;
+        let end = offsets[v as usize + 1] as usize;
+        assert!(from[start..end].iter().all(|&f| f == v));
+        assert_eq!(adjacency.get(v), to[start..end]);
+    }
+}
+
+#[test]
+fn test_from_lists() {
+    let adjacency = Adjacency::from_lists(&[vec![1, 2], vec![], vec![0, 0]]);
+    assert_eq!(adjacency.num_vertices(), 3);
+    assert_eq!(adjacency.num_edges(), 4);
+    assert_eq!(adjacency.offsets(), &[0, 2, 2, 4]);
+    assert!(adjacency.get(1).is_empty());
+    assert_eq!(adjacency.get(2).to_vec(), vec![0, 0]);
+    assert_eq!(
+        adjacency.get(0).iter().rev().collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+
+    let empty = Adjacency::default();
+    assert_eq!(empty.num_vertices(), 0);
+    assert_eq!(empty.num_edges(), 0);
+}
+
+fn directed(lists: &[Vec<u64>]) -> Graph {
+    with_direction(lists, true)
+}
+
+fn with_direction(lists: &[Vec<u64>], directed: bool) -> Graph {
+    let mut file = GraphFile::default();
+    file.directed = directed;
+    file.out_neighbors = Adjacency::from_lists(lists);
+    file.num_vertices = file.out_neighbors.num_vertices();
+    file.num_edges = file.out_neighbors.num_edges();
+    Graph::from(file)
+}
+
+#[test]
+fn test_in_neighbors() {
+    // 0 -> 1, 0 -> 2, 2 -> 1, 1 -> 1, 2 -> 0
+    let graph = directed(&[vec![1, 2], vec![1], vec![1, 0], vec![]]);
+
+    assert_eq!(graph.in_neighbors(1).to_vec(), vec![0, 1, 2]);
+    assert_eq!(graph.in_neighbors(0).to_vec(), vec![2]);
+    assert!(graph.in_neighbors(3).is_empty());
+    assert_eq!(graph.in_degree(1), 3);
+    assert_eq!(graph.out_degree(2), 2);
+    assert_eq!(graph.all_neighbors(2).collect::<Vec<_>>(), vec![1, 0, 0]);
+}
+
+#[test]
+fn test_in_neighbors_match_edges() {
+    let data = decodebuffer(include_bytes!("../test_data/network.gt.zst")).unwrap();
+    let mut file = GraphFile::try_from(data.as_slice()).unwrap();
+    file.directed = true;
+    let graph = Graph::from(file);
+
+    let (from, to) = graph.edges();
+    for v in graph.vertices() {
+        let expected: Vec<u64> = (0..from.len())
+            .filter(|&e| to[e] == v)
+            .map(|e| from[e])
+            .collect();
+        assert_eq!(graph.in_neighbors(v).to_vec(), expected);
+        assert_eq!(graph.in_degree(v), expected.len() as u64);
+    }
+}
+
+#[test]
+fn test_undirected_neighbors() {
+    // 0 - 1 stored with 0 and once more with 1, 2 - 1, self-loop at 2
+    let graph = with_direction(&[vec![1], vec![0], vec![1, 2], vec![]], false);
+
+    assert_eq!(graph.out_neighbors(0).to_vec(), vec![1, 1]);
+    assert_eq!(graph.out_neighbors(1).to_vec(), vec![0, 0, 2]);
+    assert_eq!(graph.out_neighbors(2).to_vec(), vec![1, 2, 2]);
+    assert!(graph.out_neighbors(3).is_empty());
+    assert_eq!(graph.in_neighbors(2), graph.out_neighbors(2));
+    assert_eq!(graph.all_neighbors(1).collect::<Vec<_>>(), vec![0, 0, 2]);
+
+    // Self-loops count twice
+    assert_eq!(graph.out_degree(2), 3);
+    assert_eq!(graph.in_degree(2), 3);
+    assert_eq!(graph.out_degree(1), 3);
+
+    assert_eq!(graph.out_edges(2).collect::<Vec<_>>(), vec![2, 3, 3]);
+    assert_eq!(graph.edge_index(0, 1), vec![0, 1]);
+    assert_eq!(graph.edge_index(1, 2), vec![2]);
+    assert_eq!(graph.edge_index(2, 1), vec![2]);
+    assert_eq!(graph.edge_index(2, 2), vec![3]);
+
+    // Edges are listed once
+    assert_eq!(graph.edges(), (vec![0, 1, 2, 2], vec![1, 0, 1, 2]));
+}
+
+#[test]
+fn test_undirected_network() {
+    let graph = network();
+    assert!(!graph.directed());
+
+    let degree_sum: u64 = graph.vertices().iter().map(|&v| graph.out_degree(v)).sum();
+    assert_eq!(degree_sum, 2 * graph.num_edges());
+
+    for v in graph.vertices() {
+        assert_eq!(graph.in_neighbors(v), graph.out_neighbors(v));
+        for (edge, u) in graph.out_edges(v).zip(graph.out_neighbors(v)) {
+            assert!(graph.out_neighbors(u).contains(v));
+            let (source, target) = graph.edge(edge).unwrap();
+            assert!((source, target) == (v, u) || (source, target) == (u, v));
+        }
+    }
+}
+
+#[test]
+fn test_edge_indices() {
+    // Multi-edge 0 -> 1 twice, 1 -> 0 once
+    let graph = directed(&[vec![1, 2, 1], vec![], vec![], vec![0, 3]]);
+
+    assert_eq!(graph.edge(0), Some((0, 1)));
+    assert_eq!(graph.edge(2), Some((0, 1)));
+    assert_eq!(graph.edge(3), Some((3, 0)));
+    assert_eq!(graph.edge(4), Some((3, 3)));
+    assert_eq!(graph.edge(5), None);
+
+    assert_eq!(graph.edge_index(0, 1), vec![0, 2]);
+    assert_eq!(graph.edge_index(1, 0), Vec::<u64>::new());
+    assert_eq!(graph.edge_index(3, 3), vec![4]);
+
+    assert_eq!(graph.out_edges(0).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(graph.out_edges(1).len(), 0);
+    assert_eq!(graph.out_edges(3).collect::<Vec<_>>(), vec![3, 4]);
+    assert_eq!(graph.in_edges(0).collect::<Vec<_>>(), vec![3]);
+    assert_eq!(graph.in_edges(1).collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn test_edge_indices_network() {
+    let graph = network();
+    let (from, to) = graph.edges();
+    for e in 0..graph.num_edges() {
+        let (source, target) = graph.edge(e).unwrap();
+        assert_eq!((source, target), (from[e as usize], to[e as usize]));
+        assert!(graph.edge_index(source, target).contains(&e));
+        // Undirected: the reverse direction finds the same edge
+        assert!(graph.edge_index(target, source).contains(&e));
+    }
+}
@@ -0,0 +1,70 @@
+use gt_graph_wasm::decode::decodebuffer;
+use gt_graph_wasm::{Graph, GraphFile, GtError};
+
+fn little_endian() -> Vec<u8> {
+    decodebuffer(include_bytes!("../test_data/network.gt.zst")).unwrap()
+}
+
+fn big_endian() -> Vec<u8> {
+    decodebuffer(include_bytes!("../test_data/network_be.gt.zst")).unwrap()
+}
+
+#[test]
+fn test_big_endian_matches_little_endian() {
+    let le = GraphFile::try_from(little_endian().as_slice()).unwrap();
+    let be = GraphFile::try_from(big_endian().as_slice()).unwrap();
+
+    assert_eq!(be.directed, le.directed);
+    assert_eq!(be.num_vertices, le.num_vertices);
+    assert_eq!(be.num_edges, le.num_edges);
+    assert_eq!(be.out_neighbors, le.out_neighbors);
+    assert_eq!(be.properties.len(), 8);
+    assert!(be.properties == le.properties);
+}
+
+#[test]
+fn test_big_endian_roundtrip() {
+    let data = big_endian();
+    let graph_file = GraphFile::try_from(data.as_slice()).unwrap();
+    assert_eq!(graph_file.to_bytes(), data);
+
+    let graph = Graph::try_from(include_bytes!("../test_data/network_be.gt.zst").to_vec()).unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+}
+
+#[test]
+fn test_unknown_endianness() {
+    let mut data = little_endian();
+    data[7] = 0x02;
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        GtError::UnsupportedEndianness {
+            offset: 7,
+            endianness: 2
+        }
+    );
+}
+
+#[test]
+fn test_header_fields() {
+    let le = Graph::try_from(include_bytes!("../test_data/network.gt.zst").to_vec()).unwrap();
+    let mut be =
+        Graph::try_from(include_bytes!("../test_data/network_be.gt.zst").to_vec()).unwrap();
+
+    assert_eq!((le.version(), le.endianness()), (1, 0x00));
+    assert_eq!((be.version(), be.endianness()), (1, 0x01));
+    assert!(le.comment().contains("stats: 77 vertices, 254 edges"));
+    assert_eq!(le.comment(), be.comment());
+
+    // The new comment is written in the byte order of the file
+    be.set_comment("from netzschleuder".to_string());
+    let data = be.to_bytes();
+    assert_eq!(data[8..16], 18u64.to_be_bytes());
+    assert_eq!(&data[16..34], b"from netzschleuder");
+
+    let copy = Graph::try_from(data).unwrap();
+    assert_eq!(copy.comment(), "from netzschleuder");
+    assert_eq!(copy.endianness(), 0x01);
+}
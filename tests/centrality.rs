@@ -0,0 +1,250 @@
+mod common;
+
+use common::{graph, lesmis};
+use gt_graph_wasm::centrality::{
+    BetweennessOptions, ClosenessOptions, KatzOptions, PageRankOptions,
+};
+use gt_graph_wasm::graph_file::properties::{PropertyMapType, PropertyType};
+use gt_graph_wasm::Graph;
+
+fn values(g: &Graph, name: &str, map_type: PropertyMapType) -> Vec<f64> {
+    let property = g.property_ref(name, Some(map_type)).unwrap();
+    property.as_f64_slice().unwrap().to_vec()
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-5, "{:?} != {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn test_betweenness() {
+    let mut g = lesmis();
+    g.betweenness("vb", "eb", &BetweennessOptions::default())
+        .unwrap();
+    let vb = values(&g, "vb", PropertyMapType::Vertex);
+    let label = g.property_ref("label", Some(PropertyMapType::Vertex));
+    assert_eq!(label.unwrap().as_strings().unwrap()[11], "Valjean");
+    assert!((vb[11] - 0.569989).abs() < 1e-6);
+
+    // Without normalization the edges on a shortest path add up to its length
+    let options = BetweennessOptions {
+        normalized: false,
+        ..BetweennessOptions::default()
+    };
+    g.betweenness("vb", "eb", &options).unwrap();
+    let eb: f64 = values(&g, "eb", PropertyMapType::Edge).iter().sum();
+    let vb: f64 = values(&g, "vb", PropertyMapType::Vertex).iter().sum();
+    let lengths: u64 = (0..77)
+        .map(|v| g.bfs(v).unwrap().distances.iter().sum::<u64>())
+        .sum();
+    assert!((eb - lengths as f64 / 2.0).abs() < 1e-6);
+    assert!((vb - (lengths - 77 * 76) as f64 / 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_weighted_betweenness() {
+    // The detour 0 -> 1 -> 2 is shorter than the direct edge 0 -> 2
+    let mut g = graph(true, 3, &[(0, 1), (0, 2), (1, 2)]);
+    g.new_edge_property("length", PropertyType::Int32);
+    g.set_edge_property("length", vec![1i32, 3, 1]).unwrap();
+    let options = BetweennessOptions {
+        weight: Some("length".to_string()),
+        normalized: false,
+    };
+    g.betweenness("vb", "eb", &options).unwrap();
+    assert_eq!(
+        values(&g, "vb", PropertyMapType::Vertex),
+        vec![0.0, 1.0, 0.0]
+    );
+    assert_eq!(values(&g, "eb", PropertyMapType::Edge), vec![2.0, 0.0, 2.0]);
+
+    let unweighted = BetweennessOptions {
+        weight: None,
+        normalized: false,
+    };
+    g.betweenness("vb", "eb", &unweighted).unwrap();
+    assert_eq!(values(&g, "eb", PropertyMapType::Edge), vec![1.0, 1.0, 1.0]);
+
+    g.set_edge_property("length", vec![1i32, -3, 1]).unwrap();
+    assert!(g.betweenness("vb", "eb", &options).is_err());
+}
+
+#[test]
+fn test_betweenness_rounded_weights() {
+    // 0.1 + 0.2 is not exactly 0.3, both paths from 0 to 2 are still shortest
+    let mut g = graph(true, 3, &[(0, 1), (0, 2), (1, 2)]);
+    g.new_edge_property("length", PropertyType::Double);
+    g.set_edge_property("length", vec![0.1f64, 0.3, 0.2])
+        .unwrap();
+    let options = BetweennessOptions {
+        weight: Some("length".to_string()),
+        normalized: false,
+    };
+    g.betweenness("vb", "eb", &options).unwrap();
+    assert_close(&values(&g, "vb", PropertyMapType::Vertex), &[0.0, 0.5, 0.0]);
+    assert_close(&values(&g, "eb", PropertyMapType::Edge), &[1.5, 0.5, 1.5]);
+}
+
+#[test]
+fn test_betweenness_zero_weights() {
+    // A path 0 - 1 - 2 where 0 and 1 are at the same distance from everywhere
+    let mut g = graph(false, 3, &[(0, 1), (1, 2)]);
+    g.new_edge_property("length", PropertyType::Double);
+    g.set_edge_property("length", vec![0.0f64, 1.0]).unwrap();
+    let options = BetweennessOptions {
+        weight: Some("length".to_string()),
+        normalized: false,
+    };
+    g.betweenness("vb", "eb", &options).unwrap();
+    assert_close(&values(&g, "vb", PropertyMapType::Vertex), &[0.0, 1.0, 0.0]);
+    assert_close(&values(&g, "eb", PropertyMapType::Edge), &[2.0, 2.0]);
+}
+
+#[test]
+fn test_closeness() {
+    // A star with center 0 and an isolated vertex 4
+    let mut g = graph(false, 5, &[(0, 1), (0, 2), (0, 3)]);
+    g.closeness("closeness", &ClosenessOptions::default())
+        .unwrap();
+    let closeness = values(&g, "closeness", PropertyMapType::Vertex);
+    assert_close(&closeness[..4], &[1.0, 0.6, 0.6, 0.6]);
+    assert!(closeness[4].is_nan());
+
+    let options = ClosenessOptions {
+        harmonic: true,
+        ..ClosenessOptions::default()
+    };
+    g.closeness("harmonic", &options).unwrap();
+    let harmonic = values(&g, "harmonic", PropertyMapType::Vertex);
+    assert_close(&harmonic, &[0.75, 0.5, 0.5, 0.5, 0.0]);
+
+    // Directed edges are followed forward only
+    let mut g = graph(true, 3, &[(0, 1), (1, 2)]);
+    let options = ClosenessOptions {
+        normalized: false,
+        ..ClosenessOptions::default()
+    };
+    g.closeness("closeness", &options).unwrap();
+    let closeness = values(&g, "closeness", PropertyMapType::Vertex);
+    assert_close(&closeness[..2], &[1.0 / 3.0, 1.0]);
+}
+
+#[test]
+fn test_pagerank() {
+    let mut g = lesmis();
+    g.pagerank("pagerank", &PageRankOptions::default()).unwrap();
+    let pagerank = values(&g, "pagerank", PropertyMapType::Vertex);
+    assert!((pagerank.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+    let max = pagerank.iter().cloned().fold(0.0, f64::max);
+    assert_eq!(pagerank[11], max);
+
+    // Without damping the personalization is all that counts
+    let mut g = graph(true, 3, &[(0, 1), (1, 2)]);
+    g.new_vertex_property("jump", PropertyType::Int16);
+    g.set_vertex_property("jump", vec![1i16, 0, 3]).unwrap();
+    let options = PageRankOptions {
+        damping: 0.0,
+        personalization: Some("jump".to_string()),
+        weight: None,
+    };
+    g.pagerank("pagerank", &options).unwrap();
+    let pagerank = values(&g, "pagerank", PropertyMapType::Vertex);
+    assert_close(&pagerank, &[0.25, 0.0, 0.75]);
+
+    // A cycle is uniform
+    let mut g = graph(true, 3, &[(0, 1), (1, 2), (2, 0)]);
+    g.pagerank("pagerank", &PageRankOptions::default()).unwrap();
+    let pagerank = values(&g, "pagerank", PropertyMapType::Vertex);
+    assert_close(&pagerank, &[1.0 / 3.0; 3]);
+
+    // The personalization has to sum to more than zero
+    g.new_vertex_property("jump", PropertyType::Int16);
+    assert!(g.pagerank("pagerank", &options).is_err());
+
+    // Weights must not be negative
+    g.new_edge_property("weight", PropertyType::Double);
+    g.set_edge_property("weight", vec![1.0f64, -1.0, 1.0])
+        .unwrap();
+    let options = PageRankOptions {
+        weight: Some("weight".to_string()),
+        ..PageRankOptions::default()
+    };
+    assert!(g.pagerank("pagerank", &options).is_err());
+}
+
+#[test]
+fn test_eigenvector() {
+    // A star is bipartite, plain power iteration would oscillate
+    let mut g = graph(false, 4, &[(0, 1), (0, 2), (0, 3)]);
+    let eigenvalue = g.eigenvector("eigenvector", None).unwrap();
+    assert!((eigenvalue - 3f64.sqrt()).abs() < 1e-5);
+    let leaf = 1.0 / 6f64.sqrt();
+    assert_close(
+        &values(&g, "eigenvector", PropertyMapType::Vertex),
+        &[1.0 / 2f64.sqrt(), leaf, leaf, leaf],
+    );
+
+    let mut g = lesmis();
+    let eigenvalue = g.eigenvector("eigenvector", Some("value")).unwrap();
+    assert!(eigenvalue > 0.0);
+    let eigenvector = values(&g, "eigenvector", PropertyMapType::Vertex);
+    let norm: f64 = eigenvector.iter().map(|x| x * x).sum();
+    assert!((norm - 1.0).abs() < 1e-6);
+    assert!(eigenvector.iter().all(|x| *x >= 0.0));
+}
+
+#[test]
+fn test_katz() {
+    let mut g = graph(true, 3, &[(0, 1), (1, 2)]);
+    let options = KatzOptions {
+        alpha: 0.5,
+        normalized: false,
+        ..KatzOptions::default()
+    };
+    g.katz("katz", &options).unwrap();
+    assert_close(
+        &values(&g, "katz", PropertyMapType::Vertex),
+        &[1.0, 1.5, 1.75],
+    );
+
+    g.katz("katz", &KatzOptions::default()).unwrap();
+    let katz = values(&g, "katz", PropertyMapType::Vertex);
+    assert!((katz.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-6);
+
+    // The series diverges if alpha is too large
+    let mut g = graph(true, 2, &[(0, 1), (1, 0)]);
+    let options = KatzOptions {
+        alpha: 1.5,
+        ..KatzOptions::default()
+    };
+    assert!(g.katz("katz", &options).is_err());
+}
+
+#[test]
+fn test_hits() {
+    // Vertex 0 points to all others
+    let mut g = graph(true, 4, &[(0, 1), (0, 2), (0, 3)]);
+    let eigenvalue = g.hits("authority", "hub", None).unwrap();
+    assert!((eigenvalue - 3.0).abs() < 1e-6);
+    let authority = 1.0 / 3f64.sqrt();
+    assert_close(
+        &values(&g, "authority", PropertyMapType::Vertex),
+        &[0.0, authority, authority, authority],
+    );
+    assert_close(
+        &values(&g, "hub", PropertyMapType::Vertex),
+        &[1.0, 0.0, 0.0, 0.0],
+    );
+
+    // In undirected graphs hubs and authorities are the same
+    let mut g = lesmis();
+    g.hits("authority", "hub", None).unwrap();
+    assert_close(
+        &values(&g, "authority", PropertyMapType::Vertex),
+        &values(&g, "hub", PropertyMapType::Vertex),
+    );
+    assert!(g.hits("authority", "hub", Some("label")).is_err());
+}
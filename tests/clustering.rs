@@ -0,0 +1,96 @@
+mod common;
+
+use common::{graph, lesmis};
+use gt_graph_wasm::Graph;
+
+#[test]
+fn test_undirected_clustering() {
+    // A complete graph on 0..4 without the edge 2 - 3, a pendant vertex 4,
+    // a parallel edge and a self-loop
+    let g = graph(
+        false,
+        5,
+        &[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (3, 0),
+            (3, 3),
+            (3, 4),
+        ],
+    );
+    assert_eq!(g.triangles(), vec![2, 2, 1, 1, 0]);
+    assert_eq!(
+        g.local_clustering(),
+        vec![2.0 / 3.0, 2.0 / 3.0, 1.0, 1.0 / 3.0, 0.0]
+    );
+    let (coefficient, error) = g.global_clustering();
+    assert_eq!(coefficient, 6.0 / 10.0);
+    assert!(error > 0.0);
+    assert_eq!(g.kcore_decomposition(), vec![2, 2, 2, 2, 1]);
+}
+
+#[test]
+fn test_directed_clustering() {
+    // 0 -> 1, 0 -> 2 and 1 -> 2: the out neighbors of 0 are connected once
+    let g = graph(true, 3, &[(0, 1), (0, 2), (1, 2)]);
+    assert_eq!(g.triangles(), vec![1, 0, 0]);
+    assert_eq!(g.local_clustering(), vec![0.5, 0.0, 0.0]);
+    assert_eq!(g.global_clustering().0, 0.5);
+
+    // With 2 -> 1 both directions close the triangle
+    let g = graph(true, 3, &[(0, 1), (0, 2), (1, 2), (2, 1)]);
+    assert_eq!(g.local_clustering(), vec![1.0, 0.0, 0.0]);
+
+    // The core numbers use the total degree
+    let g = graph(true, 4, &[(0, 1), (1, 2), (2, 0), (3, 0)]);
+    assert_eq!(g.kcore_decomposition(), vec![2, 2, 2, 1]);
+}
+
+#[test]
+fn test_kcore_multigraph() {
+    // A path 0 - 1 - 2 with a triple edge and self-loops is still a 1-core
+    let g = graph(
+        false,
+        3,
+        &[(0, 1), (1, 0), (0, 1), (1, 1), (1, 1), (1, 2), (2, 2)],
+    );
+    assert_eq!(g.kcore_decomposition(), vec![1, 1, 1]);
+
+    // Parallel edges count once in each direction
+    let g = graph(true, 3, &[(0, 1), (0, 1), (1, 0), (0, 0), (1, 2), (1, 2)]);
+    assert_eq!(g.kcore_decomposition(), vec![2, 2, 1]);
+}
+
+#[test]
+fn test_lesmis_clustering() {
+    let g = lesmis();
+    // The values of networkx' `transitivity` and `average_clustering`
+    let (coefficient, _) = g.global_clustering();
+    assert!((coefficient - 0.498932).abs() < 1e-6);
+    let average = g.local_clustering().iter().sum::<f64>() / 77.0;
+    assert!((average - 0.573137).abs() < 1e-6);
+
+    let cores = g.kcore_decomposition();
+    assert_eq!(cores.iter().max(), Some(&9));
+    // Every vertex of a k-core has k neighbors in it
+    for vertex in 0..77 {
+        let k = cores[vertex as usize];
+        let in_core = g
+            .out_neighbors(vertex)
+            .iter()
+            .filter(|&neighbor| cores[neighbor as usize] >= k)
+            .count();
+        assert!(in_core as u64 >= k);
+    }
+}
+
+#[test]
+fn test_empty_clustering() {
+    let g = Graph::new();
+    assert!(g.triangles().is_empty());
+    assert_eq!(g.global_clustering(), (0.0, 0.0));
+    assert!(g.kcore_decomposition().is_empty());
+}
@@ -0,0 +1,38 @@
+// Every test crate uses only some of the helpers
+#![allow(dead_code)]
+
+use gt_graph_wasm::graph_file::properties::PropertyType;
+use gt_graph_wasm::graph_file::Adjacency;
+use gt_graph_wasm::{Graph, GraphFile};
+
+/// Graph with `n` vertices and the given edges (sorted by source, so the
+/// edge indices are the positions in `edges`)
+pub fn graph(directed: bool, n: u64, edges: &[(u64, u64)]) -> Graph {
+    let mut lists = vec![Vec::new(); n as usize];
+    for &(source, target) in edges {
+        lists[source as usize].push(target);
+    }
+    let mut file = GraphFile::default();
+    file.directed = directed;
+    file.num_vertices = n;
+    file.num_edges = edges.len() as u64;
+    file.out_neighbors = Adjacency::from_lists(&lists);
+    Graph::from(file)
+}
+
+/// Graph with `n` vertices and the given weighted edges (sorted by source,
+/// so the edge indices are the positions in `edges`) in the property "weight"
+pub fn weighted_graph(directed: bool, n: u64, edges: &[(u64, u64, f64)]) -> Graph {
+    let pairs: Vec<(u64, u64)> = edges.iter().map(|&(s, t, _)| (s, t)).collect();
+    let mut graph = graph(directed, n, &pairs);
+    graph.new_edge_property("weight", PropertyType::Double);
+    let weights = edges.iter().map(|edge| edge.2).collect();
+    graph.set_edge_property("weight", weights).unwrap();
+    graph
+}
+
+/// The co-appearances in Les Misérables (undirected, 77 vertices, 254 edges)
+pub fn lesmis() -> Graph {
+    let data = include_bytes!("../../test_data/network.gt.zst").to_vec();
+    Graph::try_from(data).unwrap()
+}
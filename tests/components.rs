@@ -0,0 +1,105 @@
+mod common;
+
+use common::{graph, lesmis};
+use gt_graph_wasm::graph_file::properties::PropertyMapType;
+use gt_graph_wasm::Graph;
+
+#[test]
+fn test_strong_components() {
+    // Cycles 0 -> 1 -> 2 -> 0 and 3 <-> 4, connected by 2 -> 3; 5 is isolated
+    let g = graph(true, 6, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)]);
+    let components = g.strong_components();
+    assert_eq!(components.labels, vec![0, 0, 0, 1, 1, 2]);
+    assert_eq!(components.sizes, vec![3, 2, 1]);
+    assert_eq!(g.components(), components);
+
+    let components = g.weak_components();
+    assert_eq!(components.labels, vec![0, 0, 0, 0, 0, 1]);
+    assert_eq!(components.sizes, vec![5, 1]);
+
+    // Without the cycles every vertex is a component of its own
+    let g = graph(true, 3, &[(2, 1), (1, 0)]);
+    assert_eq!(g.strong_components().sizes, vec![1, 1, 1]);
+    assert_eq!(g.weak_components().sizes, vec![3]);
+}
+
+#[test]
+fn test_undirected_components() {
+    let g = graph(false, 5, &[(1, 0), (2, 2), (3, 4)]);
+    let components = g.components();
+    assert_eq!(components.labels, vec![0, 0, 1, 2, 2]);
+    assert_eq!(components.sizes, vec![2, 1, 2]);
+    assert_eq!(g.strong_components(), components);
+
+    // Ties go to the component with the smallest vertex
+    assert_eq!(components.largest(), Some(0));
+    assert_eq!(g.largest_component(), vec![true, true, false, false, false]);
+    assert_eq!(Graph::new().largest_component(), Vec::<bool>::new());
+}
+
+#[test]
+fn test_long_cycle() {
+    // Deep enough to overflow a recursive search on a small stack
+    let n = 100_000;
+    let edges: Vec<(u64, u64)> = (0..n).map(|v| (v, (v + 1) % n)).collect();
+    let g = graph(true, n, &edges);
+    assert_eq!(g.strong_components().sizes, vec![n]);
+
+    let g = graph(true, n, &edges[..n as usize - 1]);
+    assert_eq!(g.strong_components().sizes.len() as u64, n);
+}
+
+#[test]
+fn test_extract_largest_component() {
+    let mut g = lesmis();
+    let isolated = g.add_vertex();
+    assert_eq!(g.weak_components().sizes, vec![77, 1]);
+
+    let mask = g.largest_component();
+    assert!(!mask[isolated as usize]);
+    let largest = g.extract_largest_component();
+    assert_eq!(largest.num_vertices(), 77);
+    assert_eq!(largest.num_edges(), 254);
+    assert_eq!(largest.edges(), g.edges());
+    let labels = |g: &Graph| {
+        let label = g.property_ref("label", Some(PropertyMapType::Vertex));
+        label.unwrap().as_strings().unwrap().to_vec()
+    };
+    assert_eq!(labels(&largest)[..], labels(&g)[..77]);
+    assert_eq!(largest.graph_property_names(), g.graph_property_names());
+
+    // The original graph is not changed
+    assert_eq!(g.num_vertices(), 78);
+}
+
+#[test]
+fn test_induced_subgraph() {
+    let g = lesmis();
+    let keep: Vec<bool> = (0..77).map(|v| v % 2 == 0).collect();
+    let subgraph = g.induced_subgraph(&keep).unwrap();
+    assert_eq!(subgraph.num_vertices(), 39);
+
+    // Every edge between two kept vertices is kept, with its value
+    let values = |g: &Graph| {
+        let value = g.property_ref("value", Some(PropertyMapType::Edge));
+        value.unwrap().as_i16_slice().unwrap().to_vec()
+    };
+    let (sources, targets) = g.edges();
+    let kept: Vec<(u64, u64, i16)> = sources
+        .iter()
+        .zip(&targets)
+        .zip(values(&g))
+        .filter(|((s, t), _)| keep[**s as usize] && keep[**t as usize])
+        .map(|((s, t), value)| (s / 2, t / 2, value))
+        .collect();
+    let (sources, targets) = subgraph.edges();
+    let expected: Vec<(u64, u64, i16)> = sources
+        .into_iter()
+        .zip(targets)
+        .zip(values(&subgraph))
+        .map(|((s, t), value)| (s, t, value))
+        .collect();
+    assert_eq!(kept, expected);
+
+    assert!(g.induced_subgraph(&keep[1..]).is_err());
+}
@@ -0,0 +1,170 @@
+use std::io::Read;
+
+use gt_graph_wasm::decode::{
+    decodebuffer, decodebuffer_zip, decodebuffer_zstd, decodestream, ZstdDecoder,
+};
+use gt_graph_wasm::{Graph, GraphFile, GtError};
+
+fn network() -> Vec<u8> {
+    decodebuffer(include_bytes!("../test_data/network.gt.zst")).unwrap()
+}
+
+#[test]
+fn test_gz() {
+    let data = decodebuffer(include_bytes!("../test_data/network.gt.gz")).unwrap();
+    assert_eq!(data, network());
+}
+
+#[test]
+fn test_xz() {
+    let data = decodebuffer(include_bytes!("../test_data/network.gt.xz")).unwrap();
+    assert_eq!(data, network());
+}
+
+#[test]
+fn test_zip_first_gt_entry() {
+    // The archive starts with a README.txt, followed by network.gt and network_be.gt
+    let data = decodebuffer(include_bytes!("../test_data/network.zip")).unwrap();
+    assert_eq!(data, network());
+
+    let graph = Graph::try_from(include_bytes!("../test_data/network.zip").to_vec()).unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+}
+
+#[test]
+fn test_zip_named_entry() {
+    let data = decodebuffer_zip(
+        include_bytes!("../test_data/network.zip"),
+        Some("network_be.gt"),
+    )
+    .unwrap();
+    let be = decodebuffer(include_bytes!("../test_data/network_be.gt.zst")).unwrap();
+    assert_eq!(data, be);
+    assert_eq!(GraphFile::try_from(data.as_slice()).unwrap().num_edges, 254);
+
+    let err = decodebuffer_zip(
+        include_bytes!("../test_data/network.zip"),
+        Some("missing.gt"),
+    );
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_corrupt_input() {
+    let mut data = include_bytes!("../test_data/network.gt.gz").to_vec();
+    data.truncate(100);
+    assert!(decodebuffer(&data).is_err());
+
+    let mut data = include_bytes!("../test_data/network.gt.xz").to_vec();
+    data.truncate(100);
+    assert!(decodebuffer(&data).is_err());
+
+    assert!(decodebuffer(&[0x1f, 0x8b]).is_err());
+}
+
+#[test]
+fn test_stream_parse() {
+    let expected = network();
+    for compressed in [
+        &include_bytes!("../test_data/network.gt.zst")[..],
+        &include_bytes!("../test_data/network.gt.gz")[..],
+        &include_bytes!("../test_data/network.gt.xz")[..],
+        &include_bytes!("../test_data/network.zip")[..],
+        &expected[..],
+    ] {
+        let graph = Graph::from_reader(compressed).unwrap();
+        assert_eq!(graph.to_bytes(), expected);
+    }
+}
+
+#[test]
+fn test_zstd_stream_frames() {
+    // Two frames with a skippable frame in between
+    let data = network();
+    let (first, second) = data.split_at(1000);
+    let mut compressed = zstd::bulk::compress(first, 3).unwrap();
+    compressed.extend_from_slice(&0x184d2a50u32.to_le_bytes());
+    compressed.extend_from_slice(&4u32.to_le_bytes());
+    compressed.extend_from_slice(b"skip");
+    compressed.extend(zstd::bulk::compress(second, 3).unwrap());
+
+    let mut decoded = Vec::new();
+    ZstdDecoder::new(compressed.as_slice())
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, data);
+
+    let graph_file = GraphFile::from_reader(ZstdDecoder::new(compressed.as_slice())).unwrap();
+    assert_eq!(graph_file.to_bytes(), data);
+}
+
+#[test]
+fn test_corrupt_stream() {
+    let data = include_bytes!("../test_data/network.gt.zst");
+    let err = Graph::from_reader(&data[..data.len() / 2]).unwrap_err();
+    assert!(
+        matches!(err, GtError::Decode(_) | GtError::Truncated { .. }),
+        "{:?}",
+        err
+    );
+
+    // Uncompressed input that ends early
+    let data = network();
+    let err = Graph::from_reader(&data[..data.len() - 1]).unwrap_err();
+    assert!(matches!(err, GtError::Truncated { .. }));
+
+    assert!(decodestream(&[0x28, 0xb5][..]).is_err());
+}
+
+fn compress_with_checksum(data: &[u8]) -> Vec<u8> {
+    let mut encoder = zstd::Encoder::new(Vec::new(), 3).unwrap();
+    encoder.include_checksum(true).unwrap();
+    std::io::Write::write_all(&mut encoder, data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_zstd_checksum() {
+    let data = network();
+    let mut compressed = compress_with_checksum(&data);
+    assert_eq!(decodebuffer_zstd(&compressed).unwrap(), data);
+
+    // The checksum is stored in the last 4 bytes of the frame
+    let len = compressed.len();
+    compressed[len - 1] ^= 0xff;
+    let err = decodebuffer_zstd(&compressed).unwrap_err();
+    assert!(err.contains("frame 0"), "{}", err);
+    assert!(err.contains("checksum mismatch"), "{}", err);
+
+    let err = Graph::from_reader(compressed.as_slice()).unwrap_err();
+    assert!(matches!(err, GtError::Decode(msg) if msg.contains("checksum mismatch")));
+}
+
+#[test]
+fn test_zstd_corrupt_frame() {
+    // Second frame is truncated
+    let data = network();
+    let mut compressed = zstd::bulk::compress(&data, 3).unwrap();
+    let first = compressed.len();
+    let second = zstd::bulk::compress(&data, 3).unwrap();
+    compressed.extend_from_slice(&second[..second.len() / 2]);
+
+    let err = decodebuffer_zstd(&compressed).unwrap_err();
+    assert!(
+        err.contains(&format!("frame 1 (starting at byte {})", first)),
+        "{}",
+        err
+    );
+
+    // Invalid frame header
+    let err = decodebuffer_zstd(&[0x28, 0xb5, 0x2f, 0xfd, 0xff, 0xff]).unwrap_err();
+    assert!(err.contains("frame 0 (starting at byte 0)"), "{}", err);
+
+    // Truncated skippable frame
+    let mut skippable = 0x184d2a50u32.to_le_bytes().to_vec();
+    skippable.extend_from_slice(&100u32.to_le_bytes());
+    skippable.extend_from_slice(b"short");
+    let err = decodebuffer_zstd(&skippable).unwrap_err();
+    assert!(err.contains("truncated"), "{}", err);
+}
@@ -0,0 +1,202 @@
+use gt_graph_wasm::{Graph, GraphFile};
+
+/// Directed graph with an int32 vertex property "v" and an int16 edge property "e"
+fn file(lists: &[Vec<u8>], vertex_values: &[i32], edge_values: &[i16]) -> Vec<u8> {
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(0x01);
+    data.extend_from_slice(&(lists.len() as u64).to_le_bytes());
+    for list in lists {
+        data.extend_from_slice(&(list.len() as u64).to_le_bytes());
+        data.extend_from_slice(list);
+    }
+    data.extend_from_slice(&2u64.to_le_bytes());
+
+    data.push(0x01);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(b'v');
+    data.push(0x02);
+    for value in vertex_values {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    data.push(0x02);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(b'e');
+    data.push(0x01);
+    for value in edge_values {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data
+}
+
+fn graph(lists: &[Vec<u8>], vertex_values: &[i32], edge_values: &[i16]) -> Graph {
+    let data = file(lists, vertex_values, edge_values);
+    Graph::from(GraphFile::try_from(data.as_slice()).unwrap())
+}
+
+#[test]
+fn test_add_vertex_and_edge() {
+    // 0 -> 1 (e=10), 1 -> 2 (e=20)
+    let mut g = graph(&[vec![1], vec![2], vec![]], &[1, 2, 3], &[10, 20]);
+
+    assert_eq!(g.add_vertex(), 3);
+    assert_eq!(g.num_vertices(), 4);
+
+    // New edges get the next index, the edge of vertex 1 keeps its index
+    assert_eq!(g.add_edge(0, 3).unwrap(), 2);
+    assert_eq!(g.add_edge(3, 3).unwrap(), 3);
+    assert_eq!(g.num_edges(), 4);
+    assert_eq!(g.edge(1), Some((1, 2)));
+    assert_eq!(g.edge(2), Some((0, 3)));
+    assert_eq!(g.out_edges(0).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(g.in_neighbors(3).to_vec(), vec![0, 3]);
+
+    // The file stores the edges by source
+    let expected = file(
+        &[vec![1, 3], vec![2], vec![], vec![3]],
+        &[1, 2, 3, 0],
+        &[10, 0, 20, 0],
+    );
+    assert_eq!(g.to_bytes(), expected);
+
+    assert!(g.add_edge(0, 4).is_err());
+    assert!(g.add_edge(7, 0).is_err());
+}
+
+#[test]
+fn test_edge_properties_stay_aligned() {
+    // 0 -> 1 (e=10), 1 -> 2 (e=20)
+    let mut g = graph(&[vec![1], vec![2], vec![]], &[1, 2, 3], &[10, 20]);
+
+    let edge = g.add_edge(0, 2).unwrap();
+    let mut values = g
+        .edge_property("e".to_string())
+        .unwrap()
+        .get::<i16>()
+        .unwrap()
+        .to_vec();
+    assert_eq!(values, vec![10, 20, 0]);
+    values[edge as usize] = 30;
+    g.set_edge_property("e", values).unwrap();
+    g.add_edge(1, 0).unwrap();
+
+    // Every edge keeps its value
+    let (from, to) = g.edges();
+    assert_eq!((from, to), (vec![0, 1, 0, 1], vec![1, 2, 2, 0]));
+    assert_eq!(
+        g.edge_property("e".to_string())
+            .unwrap()
+            .get::<i16>()
+            .unwrap(),
+        &[10, 20, 30, 0]
+    );
+
+    // Removing an edge moves the later ones down
+    g.remove_edge(1).unwrap();
+    assert_eq!(g.edges(), (vec![0, 0, 1], vec![1, 2, 0]));
+    assert_eq!(
+        g.edge_property("e".to_string())
+            .unwrap()
+            .get::<i16>()
+            .unwrap(),
+        &[10, 30, 0]
+    );
+    assert_eq!(g.in_edges(2).collect::<Vec<_>>(), vec![1]);
+
+    // The file stores the edges by source, with their values
+    let expected = file(&[vec![1, 2], vec![0], vec![]], &[1, 2, 3], &[10, 30, 0]);
+    assert_eq!(g.to_bytes(), expected);
+
+    g.remove_vertex(0).unwrap();
+    assert_eq!(g.edges(), (vec![], vec![]));
+    assert!(g.adjacency().edge_order().is_none());
+}
+
+#[test]
+fn test_remove_vertex() {
+    // 0 -> 1, 0 -> 2, 1 -> 2, 2 -> 0, 3 -> 1
+    let mut g = graph(
+        &[vec![1, 2], vec![2], vec![0], vec![1]],
+        &[1, 2, 3, 4],
+        &[10, 20, 30, 40, 50],
+    );
+
+    g.remove_vertex(1).unwrap();
+    assert_eq!(g.num_vertices(), 3);
+    assert_eq!(g.num_edges(), 2);
+    assert_eq!(g.out_neighbors(0).to_vec(), vec![1]);
+    assert_eq!(g.in_neighbors(0).to_vec(), vec![1]);
+
+    let expected = file(&[vec![1], vec![0], vec![]], &[1, 3, 4], &[20, 40]);
+    assert_eq!(g.to_bytes(), expected);
+
+    assert!(g.remove_vertex(3).is_err());
+}
+
+#[test]
+fn test_retain_vertices() {
+    // 0 -> 1, 0 -> 2, 1 -> 2, 2 -> 0, 3 -> 1
+    let mut g = graph(
+        &[vec![1, 2], vec![2], vec![0], vec![1]],
+        &[1, 2, 3, 4],
+        &[10, 20, 30, 40, 50],
+    );
+
+    g.retain_vertices(&[true, false, true, true]).unwrap();
+    let expected = file(&[vec![1], vec![0], vec![]], &[1, 3, 4], &[20, 40]);
+    assert_eq!(g.to_bytes(), expected);
+    assert_eq!(g.in_neighbors(0).to_vec(), vec![1]);
+
+    assert!(g.retain_vertices(&[true]).is_err());
+    g.retain_vertices(&[false, false, false]).unwrap();
+    assert_eq!((g.num_vertices(), g.num_edges()), (0, 0));
+}
+
+#[test]
+fn test_remove_edge() {
+    let mut g = graph(&[vec![1, 2], vec![2], vec![]], &[1, 2, 3], &[10, 20, 30]);
+
+    g.remove_edge(1).unwrap();
+    assert_eq!(g.num_edges(), 2);
+    assert_eq!(g.edge(1), Some((1, 2)));
+    assert_eq!(g.in_neighbors(2).to_vec(), vec![1]);
+
+    let expected = file(&[vec![1], vec![2], vec![]], &[1, 2, 3], &[10, 30]);
+    assert_eq!(g.to_bytes(), expected);
+
+    assert!(g.remove_edge(2).is_err());
+}
+
+#[test]
+fn test_clear_edges() {
+    let mut g = graph(&[vec![1, 2], vec![2], vec![]], &[1, 2, 3], &[10, 20, 30]);
+
+    g.clear_edges();
+    assert_eq!(g.num_edges(), 0);
+    assert_eq!(g.num_vertices(), 3);
+    assert!(g.in_neighbors(2).is_empty());
+
+    let expected = file(&[vec![], vec![], vec![]], &[1, 2, 3], &[]);
+    assert_eq!(g.to_bytes(), expected);
+}
+
+#[test]
+fn test_edit_network() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut g = Graph::try_from(data).unwrap();
+
+    let degree = g.out_degree(5);
+    g.remove_vertex(5).unwrap();
+    assert_eq!(g.num_vertices(), 76);
+    assert_eq!(g.num_edges(), 254 - degree);
+
+    let v = g.add_vertex();
+    g.add_edge(v, 0).unwrap();
+
+    // The edited graph is a valid gt file with properties of the right length
+    let copy = Graph::try_from(g.to_bytes()).unwrap();
+    assert_eq!(copy.num_vertices(), 77);
+    assert_eq!(copy.num_edges(), 255 - degree);
+    assert_eq!(copy.to_bytes(), g.to_bytes());
+}
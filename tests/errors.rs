@@ -0,0 +1,151 @@
+use gt_graph_wasm::error::Section;
+use gt_graph_wasm::{Graph, GraphFile, GtError};
+
+/// Minimal valid file: 2 vertices, one edge 0 -> 1, no properties
+fn minimal_file() -> Vec<u8> {
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(b"hi");
+    data.push(0x01); // directed
+    data.extend_from_slice(&2u64.to_le_bytes()); // num vertices
+    data.extend_from_slice(&1u64.to_le_bytes()); // vertex 0: 1 neighbor
+    data.push(1);
+    data.extend_from_slice(&0u64.to_le_bytes()); // vertex 1: 0 neighbors
+    data.extend_from_slice(&0u64.to_le_bytes()); // num properties
+    data
+}
+
+#[test]
+fn test_minimal_file() {
+    let data = minimal_file();
+    let graph_file = GraphFile::try_from(data.as_slice()).unwrap();
+    assert_eq!(graph_file.num_vertices, 2);
+    assert_eq!(graph_file.num_edges, 1);
+}
+
+#[test]
+fn test_truncated_input() {
+    let data = minimal_file();
+    for len in 0..data.len() {
+        let result = GraphFile::try_from(&data[..len]);
+        assert!(
+            matches!(result, Err(GtError::Truncated { .. })),
+            "length {} gave {:?}",
+            len,
+            result
+        );
+    }
+
+    let err = GraphFile::try_from(&data[..30]).unwrap_err();
+    assert_eq!(err.section(), Some(Section::Adjacency));
+}
+
+#[test]
+fn test_bad_header() {
+    let mut data = minimal_file();
+    data[0] = 0x00;
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert!(matches!(err, GtError::BadMagic { offset: 0, .. }));
+
+    let mut data = minimal_file();
+    data[6] = 0x02;
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        GtError::UnsupportedVersion {
+            offset: 6,
+            version: 2
+        }
+    );
+}
+
+#[test]
+fn test_invalid_comment() {
+    let mut data = minimal_file();
+    data[16] = 0xff;
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        GtError::InvalidUtf8 {
+            offset: 16,
+            section: Section::Comment
+        }
+    );
+}
+
+#[test]
+fn test_neighbor_out_of_range() {
+    let mut data = minimal_file();
+    data[35] = 7;
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        GtError::NeighborOutOfRange {
+            offset: 35,
+            vertex: 0,
+            neighbor: 7,
+            num_vertices: 2
+        }
+    );
+}
+
+#[test]
+fn test_unknown_property_type() {
+    let mut data = minimal_file();
+    let len = data.len();
+    data[len - 8..].copy_from_slice(&1u64.to_le_bytes());
+    data.push(0x01); // vertex property
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(b'x');
+    data.push(0x42); // value type
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert_eq!(
+        err,
+        GtError::UnknownPropertyType {
+            offset: len as u64 + 10,
+            section: Section::Property("x".into()),
+            value: 0x42
+        }
+    );
+}
+
+#[test]
+fn test_invalid_string_property() {
+    let mut data = minimal_file();
+    let len = data.len();
+    data[len - 8..].copy_from_slice(&1u64.to_le_bytes());
+    data.push(0x00); // graph property
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(b'x');
+    data.push(0x06); // string
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(&[0xff, 0xfe]);
+
+    let expected = GtError::InvalidUtf8 {
+        offset: len as u64 + 19,
+        section: Section::Property("x".into()),
+    };
+    assert_eq!(GraphFile::try_from(data.as_slice()).unwrap_err(), expected);
+    // Lazily decoded strings are checked while loading as well
+    assert_eq!(GraphFile::from_buffer(data).unwrap_err(), expected);
+}
+
+#[test]
+fn test_corrupt_lengths_do_not_allocate() {
+    // A huge vertex count must fail instead of reserving memory
+    let mut data = minimal_file();
+    data[19..27].copy_from_slice(&u64::MAX.to_le_bytes());
+    let err = GraphFile::try_from(data.as_slice()).unwrap_err();
+    assert!(matches!(err, GtError::Truncated { .. }));
+}
+
+#[test]
+fn test_graph_try_from() {
+    let err = Graph::try_from(vec![0x28, 0xb5]).unwrap_err();
+    assert!(matches!(err, GtError::Decode(_)));
+
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = Graph::try_from(data).unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+}
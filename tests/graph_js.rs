@@ -7,7 +7,7 @@ async fn test_from_url() {
     let url = "https://networks.skewed.de/net/advogato/files/advogato.gt.zst".to_string();
 
     // Create a promise that is ready on the next tick of the micro task queue.
-    let graph = GraphJS::from_url(url).await.unwrap();
+    let graph = GraphJS::from_url(url, None).await.unwrap();
 
     assert_eq!(graph.num_vertices(), 6541);
     assert_eq!(graph.num_edges(), 51127);
@@ -19,7 +19,7 @@ async fn test_from_netzschleuder() {
     let sub_network = Some("advogato".to_string());
 
     // Create a promise that is ready on the next tick of the micro task queue.
-    let graph = GraphJS::from_netzschleuder(network, sub_network)
+    let graph = GraphJS::from_netzschleuder(network, sub_network, None)
         .await
         .unwrap();
 
@@ -188,3 +188,545 @@ async fn test_from_data() {
         Err(_) => panic!("Error reading graph"),
     }
 }
+
+#[wasm_bindgen_test]
+fn test_from_data_error() {
+    let data = js_sys::Uint8Array::from(&[0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x02, 0x00][..]);
+    let err = GraphJS::from_data(data, None, None).err().unwrap();
+
+    let err: js_sys::Error = err.into();
+    assert_eq!(err.name(), "GtError");
+    let kind = js_sys::Reflect::get(&err, &"kind".into()).unwrap();
+    assert_eq!(kind.as_string().unwrap(), "UnsupportedVersion");
+    let offset = js_sys::Reflect::get(&err, &"offset".into()).unwrap();
+    assert_eq!(offset.as_f64().unwrap(), 6.0);
+}
+
+#[wasm_bindgen_test]
+fn test_to_uint8array() {
+    let data =
+        gt_graph_wasm::decode::decodebuffer(include_bytes!("../test_data/network.gt.zst")).unwrap();
+    let graph = GraphJS::try_from(data.clone()).unwrap();
+
+    let array = graph.to_uint8array(None).unwrap();
+    assert_eq!(array.to_vec(), data);
+}
+
+#[wasm_bindgen_test]
+fn test_long_double_property() {
+    // Single vertex graph with a long double graph property "x" = -3.0
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(0x00);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(0x00);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(b'x');
+    data.push(0x05);
+    let raw = [0, 0, 0, 0, 0, 0, 0, 0xc0, 0x00, 0xc0, 0, 0, 0, 0, 0, 0];
+    data.extend_from_slice(&raw);
+
+    let graph = GraphJS::try_from(data).unwrap();
+    let values: js_sys::Float64Array = graph.graph_properties("x".into()).unwrap().into();
+    assert_eq!(values.to_vec(), vec![-3.0]);
+
+    let bytes: js_sys::Uint8Array = graph.graph_properties_raw("x".into()).unwrap().into();
+    assert_eq!(bytes.to_vec(), raw.to_vec());
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[wasm_bindgen_test]
+async fn test_from_url_stream() {
+    // A data url is streamed like any other response body
+    let data = include_bytes!("../test_data/network.gt.zst");
+    let url = format!("data:application/octet-stream;base64,{}", base64(data));
+
+    let graph = GraphJS::from_url(url.clone(), None).await.unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+
+    // The decompressed file is kept for lazy loading
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"lazy".into(), &true.into()).unwrap();
+    let graph = GraphJS::from_url(url, Some(options)).await.unwrap();
+    let info = graph.property_info();
+    let loaded = js_sys::Reflect::get(&info.get(0), &"loaded".into()).unwrap();
+    assert_eq!(loaded.as_bool(), Some(false));
+    assert_eq!(graph.num_edges(), 254);
+}
+
+#[wasm_bindgen_test]
+fn test_csr_copies() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    let offsets = graph.out_offsets().to_vec();
+    let targets: js_sys::Uint32Array = graph.out_targets().into();
+    let targets = targets.to_vec();
+    assert_eq!(offsets.len(), 78);
+    assert_eq!(targets.len(), 254);
+
+    // Edges are stored once with their source
+    let to: js_sys::BigUint64Array = graph.edges().get(1).into();
+    let to: Vec<u32> = to.to_vec().iter().map(|&t| t as u32).collect();
+    assert_eq!(targets, to);
+    assert_eq!(offsets[77], 254);
+
+    // The arrays are copies which outlive changes of the graph
+    let mut graph = graph;
+    let offsets = graph.out_offsets();
+    let targets: js_sys::Uint32Array = graph.out_targets().into();
+    graph.add_edge(0, 1).unwrap();
+    let _other = GraphJS::try_from(include_bytes!("../test_data/network.gt.zst").to_vec());
+    assert_eq!(offsets.get_index(77), 254);
+    assert_eq!(targets.length(), 254);
+    assert_eq!(graph.out_offsets().get_index(77), 255);
+}
+
+#[wasm_bindgen_test]
+fn test_csr_views() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+
+    let offsets = graph.out_offsets_view();
+    let targets = graph.out_targets_view();
+    assert_eq!(offsets.length(), 78);
+    assert_eq!(offsets.value_type(), "uint64_t");
+    assert_eq!(targets.value_type(), "uint32_t");
+
+    let array: js_sys::Uint32Array = targets.array().unwrap().into();
+    let expected: js_sys::Uint32Array = graph.out_targets().into();
+    assert_eq!(array.to_vec(), expected.to_vec());
+    grow_memory();
+
+    // The old array is detached, the view creates a new one
+    assert_eq!(array.length(), 0);
+    let array: js_sys::BigUint64Array = offsets.array().unwrap().into();
+    assert_eq!(array.to_vec(), graph.out_offsets().to_vec());
+
+    // Changing the graph invalidates the views
+    graph.add_edge(0, 1).unwrap();
+    assert!(!offsets.valid());
+    assert!(targets.array().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_edge_property_lookup() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    let edge = graph.edge(10).unwrap().to_vec();
+    let ids = graph.edge_index(edge[0], edge[1]).to_vec();
+    assert!(ids.contains(&10));
+    assert!(graph.edge(254).is_none());
+
+    let out_edges = graph.out_edges(edge[0]).to_vec();
+    assert!(out_edges.contains(&10));
+
+    let values: js_sys::Int16Array = graph.edge_properties("value".into()).unwrap().into();
+    assert_eq!(values.length(), 254);
+}
+
+#[wasm_bindgen_test]
+fn test_edit() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+
+    let v = graph.add_vertex();
+    assert_eq!(v, 77);
+    let e = graph.add_edge(0, v).unwrap();
+    assert_eq!(graph.num_edges(), 255);
+    assert!(graph.add_edge(0, 78).is_err());
+
+    let values: js_sys::Int16Array = graph.edge_properties("value".into()).unwrap().into();
+    assert_eq!(values.length(), 255);
+    assert_eq!(values.get_index(e as u32), 0);
+
+    graph.remove_vertex(v).unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+
+    graph.clear_edges();
+    assert_eq!(graph.num_edges(), 0);
+    let values: js_sys::Int16Array = graph.edge_properties("value".into()).unwrap().into();
+    assert_eq!(values.length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_new_property() {
+    use wasm_bindgen::JsValue;
+
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+
+    // From a typed array
+    let scores = js_sys::Float64Array::new_with_length(77);
+    scores.fill(0.5, 0, 77);
+    graph
+        .new_vertex_property("score".into(), "double".into(), Some(scores.into()))
+        .unwrap();
+    let values: js_sys::Float64Array = graph.vertex_properties("score".into()).unwrap().into();
+    assert_eq!(values.get_index(76), 0.5);
+
+    // From a JS array of arrays, int64 values as numbers or BigInts
+    let rows = js_sys::Array::new();
+    for i in 0..254u64 {
+        rows.push(&js_sys::Array::of2(
+            &JsValue::from(i as f64),
+            &JsValue::from(i),
+        ));
+    }
+    graph
+        .new_edge_property("pair".into(), "vector<int64_t>".into(), Some(rows.into()))
+        .unwrap();
+    let values: js_sys::Array = graph.edge_properties("pair".into()).unwrap().into();
+    let pair: js_sys::BigInt64Array = values.get(3).into();
+    assert_eq!(pair.to_vec(), vec![3, 3]);
+
+    // Graph properties take the value itself
+    graph
+        .new_graph_property("title".into(), "string".into(), Some("network".into()))
+        .unwrap();
+    let title: js_sys::Array = graph.graph_properties("title".into()).unwrap().into();
+    assert_eq!(title.get(0).as_string().unwrap(), "network");
+
+    // Wrong length, unknown type and wrong values leave the graph unchanged
+    assert!(graph
+        .set_vertex_property("score".into(), js_sys::Array::new().into())
+        .is_err());
+    assert!(graph
+        .new_vertex_property("x".into(), "complex".into(), None)
+        .is_err());
+    assert!(graph
+        .new_graph_property("x".into(), "int16_t".into(), Some("a".into()))
+        .is_err());
+    assert!(!graph.graph_property_names().includes(&"x".into(), 0));
+
+    graph.remove_edge_property("pair".into()).unwrap();
+    assert!(graph.edge_properties("pair".into()).is_err());
+}
+
+/// Grow the wasm memory by one page, which detaches all arrays over it
+///
+/// Allocating a buffer does not grow the memory if earlier tests left
+/// enough free memory behind.
+fn grow_memory() {
+    #[cfg(target_arch = "wasm32")]
+    assert_ne!(std::arch::wasm32::memory_grow(0, 1), usize::MAX);
+}
+
+#[wasm_bindgen_test]
+fn test_property_copy_after_growth() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    let values: js_sys::Int16Array = graph.edge_properties("value".into()).unwrap().into();
+    let expected = values.to_vec();
+    grow_memory();
+
+    // Copies are owned by JS and survive the growth
+    assert_eq!(values.length(), 254);
+    assert_eq!(values.to_vec(), expected);
+}
+
+#[wasm_bindgen_test]
+fn test_property_view_after_growth() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+
+    let view = graph.edge_property_view("value".into()).unwrap();
+    assert_eq!(view.length(), 254);
+    assert_eq!(view.value_type(), "int16_t");
+
+    let array: js_sys::Int16Array = view.array().unwrap().into();
+    let expected = array.to_vec();
+    grow_memory();
+
+    // The old array is detached, the view creates a new one
+    assert_eq!(array.length(), 0);
+    let array: js_sys::Int16Array = view.array().unwrap().into();
+    assert_eq!(array.to_vec(), expected);
+
+    // Writes go to the property
+    array.set_index(0, 42);
+    let values: js_sys::Int16Array = graph.edge_properties("value".into()).unwrap().into();
+    assert_eq!(values.get_index(0), 42);
+
+    // Changing the graph invalidates the view
+    graph.add_edge(0, 1).unwrap();
+    grow_memory();
+    assert!(!view.valid());
+    assert!(view.array().is_err());
+
+    // Only fixed width values can be viewed
+    assert!(graph.vertex_property_view("label".into()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_property_view_after_free() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+    let view = graph.edge_property_view("value".into()).unwrap();
+    assert!(view.valid());
+
+    drop(graph);
+    assert!(!view.valid());
+    assert!(view.array().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_property_info() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    let info = graph.property_info();
+    assert_eq!(info.length(), 8);
+
+    let get = |object: &wasm_bindgen::JsValue, key: &str| {
+        js_sys::Reflect::get(object, &key.into()).unwrap()
+    };
+    let value = info
+        .iter()
+        .find(|object| get(object, "name").as_string().unwrap() == "value")
+        .unwrap();
+    assert_eq!(get(&value, "map_type").as_string().unwrap(), "edge");
+    assert_eq!(get(&value, "value_type").as_f64().unwrap(), 1.0);
+    assert_eq!(get(&value, "type_name").as_string().unwrap(), "int16_t");
+    assert_eq!(get(&value, "length").as_f64().unwrap(), 254.0);
+    assert_eq!(get(&value, "bytes").as_f64().unwrap(), 508.0);
+}
+
+#[wasm_bindgen_test]
+fn test_header_fields() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+
+    assert_eq!(graph.version(), 1);
+    assert_eq!(graph.endianness(), "little");
+    assert!(graph.comment().starts_with("graph-tool binary file"));
+
+    graph.set_comment("edited".into());
+    let copy = GraphJS::from_data(graph.to_uint8array(None).unwrap(), None, None).unwrap();
+    assert_eq!(copy.comment(), "edited");
+}
+
+#[wasm_bindgen_test]
+fn test_read_schema() {
+    let data = include_bytes!("../test_data/network.gt.zst");
+    let data = js_sys::Uint8Array::from(&data[..]);
+    let get = |object: &wasm_bindgen::JsValue, key: &str| {
+        js_sys::Reflect::get(object, &key.into()).unwrap()
+    };
+
+    let header = GraphJS::read_header(data.clone()).unwrap();
+    assert_eq!(get(&header, "num_vertices").as_f64().unwrap(), 77.0);
+    assert_eq!(get(&header, "endianness").as_string().unwrap(), "little");
+    assert!(!get(&header, "directed").as_bool().unwrap());
+    assert!(get(&header, "num_edges").is_undefined());
+
+    let schema = GraphJS::read_schema(data).unwrap();
+    assert_eq!(get(&schema, "num_edges").as_f64().unwrap(), 254.0);
+    let properties: js_sys::Array = get(&schema, "properties").into();
+    assert_eq!(properties.length(), 8);
+
+    let pos = properties.get(6);
+    assert_eq!(get(&pos, "name").as_string().unwrap(), "_pos");
+    assert_eq!(get(&pos, "map_type").as_string().unwrap(), "vertex");
+    assert_eq!(
+        get(&pos, "type_name").as_string().unwrap(),
+        "vector<double>"
+    );
+    assert_eq!(get(&pos, "length").as_f64().unwrap(), 77.0);
+
+    // Uncompressed files are read the same way
+    let graph = GraphJS::try_from(include_bytes!("../test_data/network.gt.zst").to_vec()).unwrap();
+    let data = graph.to_uint8array(None).unwrap();
+    let header = GraphJS::read_header(data.clone()).unwrap();
+    assert_eq!(get(&header, "num_vertices").as_f64().unwrap(), 77.0);
+    let schema = GraphJS::read_schema(data).unwrap();
+    assert_eq!(get(&schema, "num_edges").as_f64().unwrap(), 254.0);
+
+    let invalid = js_sys::Uint8Array::from(&b"not a graph"[..]);
+    assert!(GraphJS::read_schema(invalid).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_load_options() {
+    let data = include_bytes!("../test_data/network.gt.zst");
+    let options = js_sys::Object::new();
+    let names = js_sys::Array::of2(&"_pos".into(), &"value".into());
+    js_sys::Reflect::set(&options, &"properties".into(), &names).unwrap();
+
+    let url = format!("data:application/octet-stream;base64,{}", base64(data));
+    let graph = GraphJS::from_url(url, Some(options.clone())).await.unwrap();
+    assert_eq!(graph.graph_property_names().length(), 0);
+    assert_eq!(graph.vertex_property_names().to_vec(), vec!["_pos"]);
+    assert_eq!(graph.edge_property_names().to_vec(), vec!["value"]);
+
+    let data = js_sys::Uint8Array::from(&data[..]);
+    let graph = GraphJS::from_data(data.clone(), None, Some(options.clone())).unwrap();
+    assert_eq!(graph.vertex_property_names().to_vec(), vec!["_pos"]);
+    let loaded = |graph: &GraphJS| {
+        let info = graph.property_info();
+        info.iter().all(|object| {
+            js_sys::Reflect::get(&object, &"loaded".into())
+                .unwrap()
+                .is_truthy()
+        })
+    };
+    assert!(loaded(&graph));
+
+    // Lazy graphs decode the properties on first access
+    js_sys::Reflect::set(&options, &"lazy".into(), &true.into()).unwrap();
+    let graph = GraphJS::from_data(data.clone(), None, Some(options.clone())).unwrap();
+    assert!(!loaded(&graph));
+    js_sys::Reflect::set(&options, &"lazy".into(), &"yes".into()).unwrap();
+    assert!(GraphJS::from_data(data.clone(), None, Some(options)).is_err());
+
+    let options = js_sys::Object::new();
+    let names = js_sys::Array::of1(&1.into());
+    js_sys::Reflect::set(&options, &"properties".into(), &names).unwrap();
+    assert!(GraphJS::from_data(data, None, Some(options)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_bfs() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    let search = graph.bfs(0).unwrap();
+    let get = |name: &str| {
+        js_sys::BigUint64Array::from(js_sys::Reflect::get(&search, &name.into()).unwrap()).to_vec()
+    };
+    assert_eq!(get("order").len(), 77);
+    assert_eq!(get("order")[0], 0);
+    assert_eq!(get("predecessors")[0], 0);
+    assert_eq!(get("distances")[0], 0);
+
+    let search = graph.dfs(0).unwrap();
+    assert!(js_sys::Reflect::has(&search, &"order".into()).unwrap());
+    assert!(graph.bfs(77).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_shortest_path() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"weight".into(), &"value".into()).unwrap();
+
+    let paths = graph.shortest_distance(0, Some(options.clone())).unwrap();
+    let distances =
+        js_sys::Float64Array::from(js_sys::Reflect::get(&paths, &"distances".into()).unwrap());
+    assert_eq!(distances.length(), 77);
+    assert_eq!(distances.get_index(0), 0.0);
+
+    let path = graph.shortest_path(0, 11, Some(options.clone())).unwrap();
+    let vertices =
+        js_sys::BigUint64Array::from(js_sys::Reflect::get(&path, &"vertices".into()).unwrap());
+    assert_eq!(vertices.get_index(0), 0);
+    assert_eq!(vertices.get_index(vertices.length() - 1), 11);
+    let distance = js_sys::Reflect::get(&path, &"distance".into()).unwrap();
+    assert_eq!(distance.as_f64(), Some(distances.get_index(11)));
+
+    js_sys::Reflect::set(&options, &"weight".into(), &"label".into()).unwrap();
+    assert!(graph.shortest_path(0, 11, Some(options)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_components() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+    graph.add_vertex();
+
+    let components = graph.weak_components();
+    let sizes =
+        js_sys::BigUint64Array::from(js_sys::Reflect::get(&components, &"sizes".into()).unwrap());
+    assert_eq!(sizes.to_vec(), vec![77, 1]);
+
+    let mask = graph.largest_component();
+    assert_eq!(mask.length(), 78);
+    assert_eq!(mask.get_index(77), 0);
+
+    let largest = graph.extract_largest_component();
+    assert_eq!(largest.num_vertices(), 77);
+    assert_eq!(largest.edge_property_names().to_vec(), vec!["value"]);
+    let subgraph = graph.induced_subgraph(mask).unwrap();
+    assert_eq!(subgraph.num_edges(), 254);
+}
+
+#[wasm_bindgen_test]
+fn test_centrality() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut graph = GraphJS::try_from(data).unwrap();
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"damping".into(), &0.5.into()).unwrap();
+    graph
+        .pagerank("pagerank".to_string(), Some(options))
+        .unwrap();
+    let pagerank =
+        js_sys::Float64Array::from(graph.vertex_properties("pagerank".to_string()).unwrap());
+    assert_eq!(pagerank.length(), 77);
+
+    graph
+        .betweenness("vb".to_string(), "eb".to_string(), None)
+        .unwrap();
+    assert!(graph.edge_property_names().includes(&"eb".into(), 0));
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"weight".into(), &"value".into()).unwrap();
+    let eigenvalue = graph
+        .eigenvector("eigenvector".to_string(), Some(options))
+        .unwrap();
+    assert!(eigenvalue > 0.0);
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"weight".into(), &1.into()).unwrap();
+    assert!(graph
+        .hits("authority".to_string(), "hub".to_string(), Some(options))
+        .is_err());
+
+    // Saved with the graph
+    let data = graph.to_uint8array(None).unwrap().to_vec();
+    let copy = GraphJS::try_from(data).unwrap();
+    assert!(copy.vertex_property_names().includes(&"vb".into(), 0));
+
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"alpha".into(), &"large".into()).unwrap();
+    assert!(graph.katz("katz".to_string(), Some(options)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_clustering() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let graph = GraphJS::try_from(data).unwrap();
+
+    assert_eq!(graph.triangles().length(), 77);
+    assert_eq!(graph.local_clustering().length(), 77);
+    let cores = graph.kcore_decomposition().to_vec();
+    assert_eq!(cores.iter().max(), Some(&9));
+
+    let global = graph.global_clustering();
+    let coefficient = js_sys::Reflect::get(&global, &"coefficient".into()).unwrap();
+    assert!((coefficient.as_f64().unwrap() - 0.498932).abs() < 1e-6);
+}
@@ -0,0 +1,79 @@
+use gt_graph_wasm::graph_file::long_double::{decode, encode};
+
+/// Build an extended precision value from its parts
+fn extended(sign: u128, exponent: u128, mantissa: u64) -> u128 {
+    (sign << 79) | (exponent << 64) | mantissa as u128
+}
+
+#[test]
+fn test_known_values() {
+    assert_eq!(encode(1.0), extended(0, 0x3fff, 1 << 63));
+    assert_eq!(encode(-2.0), extended(1, 0x4000, 1 << 63));
+    assert_eq!(encode(0.0), 0);
+    assert_eq!(decode(extended(0, 0x3fff, 1 << 63)), 1.0);
+    assert_eq!(decode(extended(0, 0x4000, 0xc000_0000_0000_0000)), 3.0);
+    assert_eq!(decode(extended(1, 0x3ffd, 1 << 63)), -0.25);
+}
+
+#[test]
+fn test_roundtrip_f64() {
+    let values = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        std::f64::consts::PI,
+        1e300,
+        -1e-300,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::MIN_POSITIVE / 3.0,
+        f64::from_bits(1),
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    for value in values {
+        let decoded = decode(encode(value));
+        assert_eq!(decoded.to_bits(), value.to_bits(), "{}", value);
+    }
+    assert!(decode(encode(f64::NAN)).is_nan());
+}
+
+#[test]
+fn test_rounding() {
+    let one = 1u64 << 63;
+    // below half an ulp
+    assert_eq!(decode(extended(0, 0x3fff, one | 1)), 1.0);
+    // exactly half an ulp: ties to even
+    assert_eq!(decode(extended(0, 0x3fff, one | 1 << 10)), 1.0);
+    assert_eq!(
+        decode(extended(0, 0x3fff, one | 3 << 10)),
+        1.0 + 2.0 * f64::EPSILON
+    );
+    // rounding up may carry into the exponent
+    assert_eq!(decode(extended(0, 0x3fff, u64::MAX)), 2.0);
+}
+
+#[test]
+fn test_out_of_range() {
+    assert_eq!(decode(extended(0, 0x7ffe, 1 << 63)), f64::INFINITY);
+    assert_eq!(decode(extended(1, 0x7ffe, 1 << 63)), f64::NEG_INFINITY);
+    assert_eq!(decode(extended(0, 0x0001, 1 << 63)), 0.0);
+    // extended denormal
+    assert_eq!(decode(extended(0, 0, 1)), 0.0);
+    // f64 denormals
+    let min = f64::from_bits(1);
+    assert_eq!(decode(encode(min) + (1 << 62)), 2.0 * min); // 1.5 * min, ties to even
+    assert_eq!(decode(extended(0, 0x3fff - 1075, 1 << 63)), 0.0); // min / 2
+    assert_eq!(decode(extended(0, 0x3fff - 1075, 3 << 62)), min); // min * 0.75
+}
+
+#[test]
+fn test_special_values() {
+    assert_eq!(decode(extended(0, 0x7fff, 1 << 63)), f64::INFINITY);
+    assert_eq!(decode(extended(1, 0x7fff, 1 << 63)), f64::NEG_INFINITY);
+    let nan = decode(extended(1, 0x7fff, 0xc000_0000_0000_0000));
+    assert!(nan.is_nan() && nan.is_sign_negative());
+    // padding bytes are ignored
+    assert_eq!(decode(extended(0, 0x3fff, 1 << 63) | 0xabcd << 96), 1.0);
+}
@@ -0,0 +1,368 @@
+use gt_graph_wasm::graph_file::properties::{PropertyMapType, PropertyType, PropertyValue};
+use gt_graph_wasm::{Graph, GraphFile, LoadOptions};
+
+/// Directed graph with 3 vertices and 2 edges (0 -> 1, 1 -> 2)
+fn graph() -> Graph {
+    let mut graph = Graph::new();
+    for _ in 0..3 {
+        graph.add_vertex();
+    }
+    graph.add_edge(0, 1).unwrap();
+    graph.add_edge(1, 2).unwrap();
+    graph
+}
+
+/// A serialized property (map type, name, value type and values)
+fn property(map_type: u8, name: &str, value_type: u8, values: &[u8]) -> Vec<u8> {
+    let mut data = vec![map_type];
+    data.extend_from_slice(&(name.len() as u64).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+    data.push(value_type);
+    data.extend_from_slice(values);
+    data
+}
+
+#[test]
+fn test_new_property() {
+    let mut g = graph();
+    g.new_vertex_property("x", PropertyType::Double);
+    g.new_edge_property("w", PropertyType::VectorInt32);
+    g.new_graph_property("title", PropertyType::String);
+
+    assert_eq!(g.vertex_property_names(), vec!["x"]);
+    assert_eq!(g.edge_property("w".into()).unwrap().len(), 2);
+
+    let mut expected = property(0x01, "x", 0x04, &[0; 24]);
+    expected.extend(property(0x02, "w", 0x09, &[0; 16]));
+    expected.extend(property(0x00, "title", 0x06, &[0; 8]));
+    assert!(g.to_bytes().ends_with(&expected));
+}
+
+#[test]
+fn test_set_property() {
+    let mut g = graph();
+    g.new_vertex_property("x", PropertyType::Double);
+    g.set_vertex_property("x", vec![0.5, 1.0, 2.0]).unwrap();
+    g.new_edge_property("e", PropertyType::Int16);
+    g.set_edge_property("e", vec![-1i16, 7]).unwrap();
+    g.new_graph_property("title", PropertyType::String);
+    g.set_graph_property("title", "abc".to_string()).unwrap();
+
+    let mut expected = property(
+        0x01,
+        "x",
+        0x04,
+        &[0.5f64, 1.0, 2.0].map(f64::to_le_bytes).concat(),
+    );
+    expected.extend(property(
+        0x02,
+        "e",
+        0x01,
+        &[-1i16, 7].map(i16::to_le_bytes).concat(),
+    ));
+    expected.extend(property(0x00, "title", 0x06, b"\x03\0\0\0\0\0\0\0abc"));
+    assert!(g.to_bytes().ends_with(&expected));
+
+    // Added vertices get the default value
+    g.add_vertex();
+    assert_eq!(g.vertex_property("x".into()).unwrap().len(), 4);
+}
+
+#[test]
+fn test_set_property_errors() {
+    let mut g = graph();
+    g.new_vertex_property("x", PropertyType::Int32);
+
+    // One value per vertex
+    let error = g.set_vertex_property("x", vec![1i32, 2]).unwrap_err();
+    assert!(error.contains("Expected 3 values"), "{}", error);
+
+    // Wrong value type
+    let error = g.set_vertex_property("x", vec![1i64, 2, 3]).unwrap_err();
+    assert!(error.contains("int32_t"), "{}", error);
+
+    assert!(g.set_vertex_property("y", vec![1i32, 2, 3]).is_err());
+    assert!(g.set_edge_property("x", vec![1i32, 2]).is_err());
+}
+
+#[test]
+fn test_long_double_property() {
+    let mut g = graph();
+    g.new_graph_property("ld", PropertyType::LongDouble);
+    g.set_graph_property("ld", 1.0).unwrap();
+
+    // 1.0 as x87 extended precision
+    let mut value = vec![0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f];
+    value.extend_from_slice(&[0; 6]);
+    assert!(g.to_bytes().ends_with(&property(0x00, "ld", 0x05, &value)));
+}
+
+#[test]
+fn test_replace_and_remove_property() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut g = Graph::try_from(data).unwrap();
+    let names = g.property_names(None);
+
+    // Replacing keeps the position but changes the type
+    g.new_edge_property("value", PropertyType::Double);
+    assert_eq!(g.property_names(None), names);
+    g.set_edge_property("value", vec![1.5; 254]).unwrap();
+    assert!(g.set_edge_property("value", vec![1i16; 254]).is_err());
+
+    let removed = g.remove_vertex_property("label").unwrap();
+    assert_eq!(removed.name, "label");
+    assert_eq!(removed.map_type, PropertyMapType::Vertex);
+    assert!(!g.vertex_property_names().contains(&"label".to_string()));
+    assert!(g.remove_vertex_property("label").is_err());
+
+    let copy = Graph::try_from(g.to_bytes()).unwrap();
+    assert_eq!(copy.property_names(None).len(), names.len() - 1);
+}
+
+#[test]
+fn test_property_type_names() {
+    assert_eq!(PropertyType::try_from("double"), Ok(PropertyType::Double));
+    assert_eq!(PropertyType::try_from("float"), Ok(PropertyType::Double));
+    assert_eq!(PropertyType::try_from("int"), Ok(PropertyType::Int32));
+    assert_eq!(
+        PropertyType::try_from("vector<long double>"),
+        Ok(PropertyType::VectorLongDouble)
+    );
+    assert_eq!(
+        PropertyType::try_from("vector<string>"),
+        Ok(PropertyType::VectorString)
+    );
+    assert_eq!(PropertyType::try_from("object"), Ok(PropertyType::PyObject));
+    assert!(PropertyType::try_from("vector<object>").is_err());
+    assert!(PropertyType::try_from("complex").is_err());
+
+    for index in 0..=0x0e {
+        let value_type = PropertyType::try_from(index).unwrap();
+        assert_eq!(PropertyType::try_from(value_type.name()), Ok(value_type));
+        assert_eq!(u8::from(value_type), index);
+    }
+}
+
+#[test]
+fn test_typed_access() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let mut g = Graph::try_from(data).unwrap();
+
+    let name = g.graph_property("name".into()).unwrap();
+    assert_eq!(name.value_type(), PropertyType::String);
+    assert_eq!(name.as_strings().unwrap(), ["lesmis"]);
+
+    let tags = g.graph_property("tags".into()).unwrap();
+    assert_eq!(
+        tags.as_vec_strings().unwrap()[0],
+        ["Social", "Fictional", "Weighted"]
+    );
+
+    let label = g.vertex_property("label".into()).unwrap();
+    assert_eq!(label.len(), 77);
+    assert_eq!(label.value(1), Some(PropertyValue::String("Napoleon")));
+    assert_eq!(label.value(77), None);
+
+    let pos = g.vertex_property("_pos".into()).unwrap();
+    assert_eq!(pos.value_type(), PropertyType::VectorDouble);
+    assert!(pos.as_vec_f64().unwrap().iter().all(|xy| xy.len() == 2));
+    assert!(pos.as_f64_slice().is_none());
+
+    let value = g.edge_property("value".into()).unwrap();
+    assert_eq!(value.get::<i16>().unwrap()[..2], [1, 8]);
+    assert_eq!(value.values().count(), 254);
+    assert!(value.as_i32_slice().is_none());
+}
+
+#[test]
+fn test_get_type_mismatch() {
+    let mut g = graph();
+    g.new_vertex_property("xy", PropertyType::VectorInt32);
+    g.set_vertex_property("xy", vec![vec![1, 2], vec![], vec![3]])
+        .unwrap();
+
+    let xy = g.vertex_property("xy".into()).unwrap();
+    assert_eq!(xy.as_vec_i32().unwrap()[2], [3]);
+    assert_eq!(xy.value(0), Some(PropertyValue::VectorInt32(&[1, 2])));
+
+    let error = xy.get::<Vec<i64>>().unwrap_err();
+    assert!(error.contains("vector<int32_t>"), "{}", error);
+    assert!(xy.get::<i32>().is_err());
+}
+
+#[test]
+fn test_get_python_object() {
+    let mut g = graph();
+    g.new_vertex_property("object", PropertyType::PyObject);
+    g.set_vertex_property("object", vec![vec![128u8, 4], vec![], vec![78]])
+        .unwrap();
+
+    // Pickled objects share the storage of vector<bool> but are not bools
+    let object = g.vertex_property("object".into()).unwrap();
+    let error = object.get::<Vec<u8>>().unwrap_err();
+    assert!(error.contains("python::object"), "{}", error);
+    assert!(object.as_vec_bool().is_none());
+    assert_eq!(object.as_objects().unwrap()[2], [78]);
+}
+
+#[test]
+fn test_get_long_double() {
+    let mut g = graph();
+    g.new_edge_property("ld", PropertyType::LongDouble);
+    g.set_edge_property("ld", vec![0.25, -3.0]).unwrap();
+    g.new_edge_property("vld", PropertyType::VectorLongDouble);
+    g.set_edge_property("vld", vec![vec![1.0], vec![2.0, 0.5]])
+        .unwrap();
+
+    let ld = g.edge_property("ld".into()).unwrap();
+    assert_eq!(ld.as_f64_slice().unwrap(), [0.25, -3.0]);
+    assert_eq!(ld.value(1), Some(PropertyValue::LongDouble(-3.0)));
+
+    // Vector long doubles are only accessible value by value
+    let vld = g.edge_property("vld".into()).unwrap();
+    assert!(vld.as_vec_f64().is_none());
+    assert_eq!(
+        vld.value(1),
+        Some(PropertyValue::VectorLongDouble(&[2.0, 0.5]))
+    );
+}
+
+#[test]
+fn test_property_info() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let options = LoadOptions::default().with_lazy(true);
+    let g = Graph::from_bytes_with(data, &options).unwrap();
+
+    let info = g.property_info();
+    assert_eq!(info.len(), g.property_names(None).len());
+
+    let label = info.iter().find(|info| info.name == "label").unwrap();
+    assert_eq!(label.map_type, PropertyMapType::Vertex);
+    assert_eq!(label.value_type, PropertyType::String);
+    assert_eq!(label.len, 77);
+    assert!(!label.loaded);
+
+    // Before decoding, the size in the file is reported
+    let pos = info.iter().find(|info| info.name == "_pos").unwrap();
+    assert_eq!(u8::from(pos.value_type), 0x0b);
+    assert_eq!(pos.bytes, 77 * (8 + 2 * 8));
+
+    for name in ["label", "_pos"] {
+        g.property_ref(name, None).unwrap().values().count();
+    }
+    let info = g.property_info();
+
+    let label = info.iter().find(|info| info.name == "label").unwrap();
+    assert!(label.loaded);
+    assert!(label.bytes > 77 * std::mem::size_of::<String>());
+
+    let pos = info.iter().find(|info| info.name == "_pos").unwrap();
+    assert_eq!(pos.bytes, 77 * (std::mem::size_of::<Vec<f64>>() + 2 * 8));
+}
+
+#[test]
+fn test_property_info_same_name() {
+    let mut g = graph();
+    g.new_vertex_property("x", PropertyType::Bool);
+    g.new_edge_property("x", PropertyType::Int64);
+
+    let info = g.property_info();
+    assert_eq!(g.property_names(None), ["x", "x"]);
+    assert_eq!(info[0].map_type, PropertyMapType::Vertex);
+    assert_eq!((info[0].len, info[0].bytes), (3, 3));
+    assert_eq!(info[1].map_type, PropertyMapType::Edge);
+    assert_eq!((info[1].len, info[1].bytes), (2, 16));
+}
+
+#[test]
+fn test_lazy_properties() {
+    let mut g = graph();
+    g.new_vertex_property("name", PropertyType::String);
+    let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    g.set_vertex_property("name", names).unwrap();
+    g.new_edge_property("w", PropertyType::VectorDouble);
+    g.set_edge_property("w", vec![vec![0.5], vec![]]).unwrap();
+    let data = g.to_bytes();
+
+    // By default everything is decoded while loading
+    let eager = Graph::try_from(data.clone()).unwrap();
+    assert!(eager.property_info().iter().all(|info| info.loaded));
+
+    let options = LoadOptions::default().with_lazy(true);
+    let mut lazy = Graph::from_bytes_with(data.clone(), &options).unwrap();
+    assert!(lazy.property_info().iter().all(|info| !info.loaded));
+    // Values which were not decoded are written as they are
+    assert_eq!(lazy.to_bytes(), data);
+
+    let name = lazy.property_ref("name", None).unwrap();
+    assert_eq!(name.as_strings().unwrap(), ["a", "b", "c"]);
+    assert!(name.is_loaded());
+    let info = lazy.property_info();
+    assert!(!info.iter().find(|info| info.name == "w").unwrap().loaded);
+    // Getting a property decodes it
+    assert!(lazy.property_ref("w", None).unwrap().is_loaded());
+
+    assert_eq!(
+        GraphFile::from_buffer(data.clone()).unwrap().properties,
+        GraphFile::try_from(data.as_slice()).unwrap().properties
+    );
+
+    lazy.add_vertex();
+    lazy.remove_edge(0).unwrap();
+    let copy = Graph::try_from(lazy.to_bytes()).unwrap();
+    let name = copy.property_ref("name", None).unwrap();
+    assert_eq!(name.as_strings().unwrap(), ["a", "b", "c", ""]);
+    let w = copy.property_ref("w", None).unwrap();
+    assert_eq!(w.value(0), Some(PropertyValue::VectorDouble(&[])));
+}
+
+#[test]
+fn test_lazy_zero_copy() {
+    let mut g = graph();
+    g.new_vertex_property("x", PropertyType::Double);
+    g.set_vertex_property("x", vec![0.5, 1.0, 2.0]).unwrap();
+    // Move the values to an offset aligned for f64
+    let offset = g.to_bytes().len() - 24;
+    let comment = format!("{}{}", g.comment(), " ".repeat((8 - offset % 8) % 8));
+    g.set_comment(comment);
+    let data = g.to_bytes();
+    assert_eq!((data.len() - 24) % 8, 0);
+
+    // The allocator aligns the buffer to (at least) 8 bytes
+    let values = data.as_ptr() as usize + data.len() - 24;
+    let options = LoadOptions::default().with_lazy(true);
+    let mut lazy = Graph::from_bytes_with(data, &options).unwrap();
+    let x = lazy.property_ref("x", None).unwrap();
+    assert_eq!(x.as_f64_slice().unwrap(), [0.5, 1.0, 2.0]);
+    assert_eq!(x.as_f64_slice().unwrap().as_ptr() as usize, values);
+
+    // Changing the values copies them out of the file
+    lazy.add_vertex();
+    let x = lazy.property_ref("x", None).unwrap();
+    assert_eq!(x.as_f64_slice().unwrap(), [0.5, 1.0, 2.0, 0.0]);
+    assert_ne!(x.as_f64_slice().unwrap().as_ptr() as usize, values);
+}
+
+#[test]
+fn test_load_options() {
+    let data = include_bytes!("../test_data/network.gt.zst").to_vec();
+    let options = LoadOptions::default().with_properties(["_pos", "name", "missing"]);
+
+    let lazy = Graph::from_bytes_with(data.clone(), &options.clone().with_lazy(true)).unwrap();
+    let eager = Graph::from_bytes_with(data.clone(), &options).unwrap();
+    let streamed = Graph::from_reader_with(&data[..], &options.clone().with_lazy(true)).unwrap();
+    assert!(streamed.property_info().iter().all(|info| !info.loaded));
+    for graph in [&lazy, &eager, &streamed] {
+        assert_eq!(graph.property_names(None), vec!["name", "_pos"]);
+        assert_eq!(graph.num_edges(), 254);
+    }
+    let pos = eager.property_ref("_pos", None).unwrap();
+    assert_eq!(pos.as_vec_f64().unwrap().len(), 77);
+
+    let copy = Graph::try_from(lazy.to_bytes()).unwrap();
+    assert_eq!(copy.property_names(None), vec!["name", "_pos"]);
+
+    let none = LoadOptions::default().with_properties(Vec::<String>::new());
+    let graph = Graph::from_bytes_with(data, &none).unwrap();
+    assert!(graph.property_info().is_empty());
+}
@@ -0,0 +1,172 @@
+use gt_graph_wasm::decode::decodebuffer;
+use gt_graph_wasm::graph_file::properties::{PropertyMapType, PropertyType};
+use gt_graph_wasm::{Graph, GraphFile, GtError};
+
+fn network() -> Vec<u8> {
+    decodebuffer(include_bytes!("../test_data/network.gt.zst")).unwrap()
+}
+
+#[test]
+fn test_schema_matches_graph() {
+    let data = network();
+    let schema = GraphFile::read_schema(&data).unwrap();
+    let graph = Graph::try_from(data.clone()).unwrap();
+
+    assert_eq!(schema.header.version, graph.version());
+    assert_eq!(schema.header.endianness, graph.endianness());
+    assert_eq!(schema.header.comment, graph.comment());
+    assert_eq!(schema.header.directed, graph.directed());
+    assert_eq!(schema.header.num_vertices, graph.num_vertices());
+    assert_eq!(schema.num_edges, graph.num_edges());
+
+    let info = graph.property_info();
+    assert_eq!(schema.properties.len(), info.len());
+    for (schema, info) in schema.properties.iter().zip(&info) {
+        assert_eq!(schema.name, info.name);
+        assert_eq!(schema.map_type, info.map_type);
+        assert_eq!(schema.value_type, info.value_type);
+        assert_eq!(schema.len, info.len as u64);
+    }
+
+    // The last property ends with the file
+    let last = schema.properties.last().unwrap();
+    assert_eq!(last.offset + last.size, data.len() as u64);
+}
+
+#[test]
+fn test_schema_byte_ranges() {
+    let data = network();
+    let schema = GraphFile::read_schema(&data).unwrap();
+
+    let value = schema
+        .properties
+        .iter()
+        .find(|p| p.name == "value")
+        .unwrap();
+    assert_eq!(value.map_type, PropertyMapType::Edge);
+    assert_eq!(value.value_type, PropertyType::Int16);
+    assert_eq!(value.size, 254 * 2);
+
+    let mut graph = Graph::try_from(data.clone()).unwrap();
+    let values = graph
+        .edge_property("value".into())
+        .unwrap()
+        .as_i16_slice()
+        .unwrap();
+    let start = value.offset as usize;
+    assert_eq!(data[start..start + 2], values[0].to_le_bytes());
+
+    // Each position holds two doubles and their count
+    let pos = schema.properties.iter().find(|p| p.name == "_pos").unwrap();
+    assert_eq!(pos.size, 77 * (8 + 2 * 8));
+}
+
+#[test]
+fn test_schema_big_endian() {
+    let le = Graph::read_schema(include_bytes!("../test_data/network.gt.zst")).unwrap();
+    let be = Graph::read_schema(include_bytes!("../test_data/network_be.gt.zst")).unwrap();
+
+    assert_eq!(be.header.endianness, 0x01);
+    assert_eq!(be.num_edges, le.num_edges);
+    assert_eq!(be.properties, le.properties);
+}
+
+#[test]
+fn test_schema_compressed_and_streamed() {
+    let data = network();
+    let schema = GraphFile::read_schema(&data).unwrap();
+
+    assert_eq!(
+        GraphFile::read_schema_from_reader(data.as_slice()).unwrap(),
+        schema
+    );
+    assert_eq!(
+        Graph::read_schema(include_bytes!("../test_data/network.gt.zst")).unwrap(),
+        schema
+    );
+    assert_eq!(Graph::read_schema(&data).unwrap(), schema);
+    assert_eq!(
+        Graph::read_schema_from_reader(&include_bytes!("../test_data/network.gt.zst")[..]).unwrap(),
+        schema
+    );
+    assert_eq!(
+        Graph::read_schema_from_reader(data.as_slice()).unwrap(),
+        schema
+    );
+}
+
+#[test]
+fn test_read_header() {
+    let data = network();
+    let schema = GraphFile::read_schema(&data).unwrap();
+
+    // The header is read even if the rest of the file is missing
+    let end = 8 + 8 + schema.header.comment.len() + 1 + 8;
+    assert_eq!(GraphFile::read_header(&data[..end]).unwrap(), schema.header);
+    assert_eq!(
+        Graph::read_header(include_bytes!("../test_data/network.gt.zst")).unwrap(),
+        schema.header
+    );
+    assert_eq!(
+        Graph::read_header_from_reader(&data[..end]).unwrap(),
+        schema.header
+    );
+
+    assert!(matches!(
+        GraphFile::read_header(&data[..end - 1]),
+        Err(GtError::Truncated { .. })
+    ));
+}
+
+#[test]
+fn test_schema_truncated() {
+    let data = network();
+    for end in [100, 1000, data.len() - 1] {
+        let err = GraphFile::read_schema(&data[..end]).unwrap_err();
+        assert!(matches!(err, GtError::Truncated { .. }), "{:?}", err);
+        let err = GraphFile::read_schema_from_reader(&data[..end]).unwrap_err();
+        assert!(matches!(err, GtError::Truncated { .. }), "{:?}", err);
+    }
+}
+
+#[test]
+fn test_schema_property_types() {
+    // A graph with one vertex and one graph property of every variable width type
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(0x01);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    let vector = |len: u64, values: &[u8]| [&len.to_le_bytes()[..], values].concat();
+    let mut vector_string = 2u64.to_le_bytes().to_vec();
+    vector_string.extend(vector(1, b"a"));
+    vector_string.extend(vector(0, b""));
+    let properties: [(u8, Vec<u8>); 8] = [
+        (0x06, vector(3, b"abc")),
+        (0x07, vector(3, &[1, 0, 1])),
+        (0x08, vector(1, &[0; 2])),
+        (0x09, vector(1, &[0; 4])),
+        (0x0a, vector(2, &[0; 16])),
+        (0x0c, vector(1, &[0; 16])),
+        (0x0d, vector_string),
+        (0x0e, vector(4, b"\x80\x04N.")),
+    ];
+
+    data.extend_from_slice(&(properties.len() as u64).to_le_bytes());
+    for (value_type, payload) in &properties {
+        data.push(0x00);
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.push(b'p');
+        data.push(*value_type);
+        data.extend_from_slice(payload);
+    }
+
+    let schema = GraphFile::read_schema(&data).unwrap();
+    for (schema, (value_type, payload)) in schema.properties.iter().zip(&properties) {
+        assert_eq!(u8::from(schema.value_type), *value_type);
+        assert_eq!(schema.size, payload.len() as u64);
+        let start = schema.offset as usize;
+        assert_eq!(&data[start..start + payload.len()], payload.as_slice());
+    }
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use common::{lesmis, weighted_graph};
+use gt_graph_wasm::graph_file::properties::{PropertyMapType, PropertyType};
+use gt_graph_wasm::shortest_paths::PathOptions;
+
+fn weighted(name: &str) -> PathOptions {
+    PathOptions {
+        weight: Some(name.to_string()),
+        ..PathOptions::default()
+    }
+}
+
+#[test]
+fn test_dijkstra() {
+    // The direct edge 0 -> 3 is longer than the detour over 1 and 2
+    let g = weighted_graph(
+        true,
+        5,
+        &[
+            (0, 1, 1.0),
+            (0, 3, 5.0),
+            (1, 2, 1.5),
+            (2, 3, 0.5),
+            (3, 4, 2.0),
+        ],
+    );
+    let paths = g.dijkstra(0, Some("weight")).unwrap();
+    assert_eq!(paths.distances, vec![0.0, 1.0, 2.5, 3.0, 5.0]);
+    assert_eq!(paths.predecessors, vec![0, 0, 1, 2, 3]);
+
+    // Edges are followed in their direction only
+    let paths = g.dijkstra(3, Some("weight")).unwrap();
+    assert_eq!(
+        paths.distances,
+        vec![f64::INFINITY, f64::INFINITY, f64::INFINITY, 0.0, 2.0]
+    );
+    assert_eq!(paths.predecessors, vec![0, 1, 2, 3, 3]);
+
+    // Without weights the distances count the edges
+    let paths = g.dijkstra(0, None).unwrap();
+    assert_eq!(paths.distances, vec![0.0, 1.0, 2.0, 1.0, 2.0]);
+
+    assert!(g.dijkstra(5, None).is_err());
+    assert!(g.dijkstra(0, Some("missing")).is_err());
+}
+
+#[test]
+fn test_integer_weights() {
+    let g = lesmis();
+    let values = g
+        .property_ref("value", Some(PropertyMapType::Edge))
+        .unwrap()
+        .as_i16_slice()
+        .unwrap()
+        .to_vec();
+    let paths = g.dijkstra(0, Some("value")).unwrap();
+    let unweighted = g.bfs(0).unwrap();
+    for vertex in 0..g.num_vertices() {
+        let distance = paths.distances[vertex as usize];
+        assert!(distance >= unweighted.distances[vertex as usize] as f64);
+        // No edge leads to a shorter distance
+        for (edge, neighbor) in g.out_edges(vertex).zip(g.out_neighbors(vertex)) {
+            assert!(paths.distances[neighbor as usize] <= distance + values[edge as usize] as f64);
+        }
+    }
+
+    // Labels can't be weights
+    assert!(g.dijkstra(0, Some("label")).is_err());
+}
+
+#[test]
+fn test_bellman_ford() {
+    let mut g = weighted_graph(
+        true,
+        4,
+        &[(0, 1, 4.0), (0, 2, 1.0), (1, 3, 1.0), (2, 1, -2.0)],
+    );
+    assert!(g.dijkstra(0, Some("weight")).is_err());
+    let paths = g.bellman_ford(0, Some("weight")).unwrap();
+    assert_eq!(paths.distances, vec![0.0, -1.0, 1.0, 0.0]);
+    assert_eq!(paths.predecessors, vec![0, 2, 0, 1]);
+
+    // 1 -> 3 -> 2 -> 1 has the weight -1
+    g.add_edge(3, 2).unwrap();
+    let weights = vec![4.0, 1.0, 1.0, -2.0, 0.0];
+    g.set_edge_property("weight", weights).unwrap();
+    let error = g.bellman_ford(0, Some("weight")).unwrap_err();
+    assert!(error.contains("negative cycle"));
+
+    // In undirected graphs a negative edge is a cycle by itself
+    let g = weighted_graph(false, 2, &[(0, 1, -1.0)]);
+    assert!(g.bellman_ford(0, Some("weight")).is_err());
+}
+
+#[test]
+fn test_shortest_path() {
+    // Two parallel edges from 0 to 1, the lighter one is used
+    let g = weighted_graph(
+        false,
+        4,
+        &[(0, 1, 3.0), (0, 1, 1.0), (1, 2, 1.0), (2, 0, 5.0)],
+    );
+    let path = g.shortest_path(0, 2, &weighted("weight")).unwrap().unwrap();
+    assert_eq!(path.vertices, vec![0, 1, 2]);
+    assert_eq!(path.edges, vec![1, 2]);
+    assert_eq!(path.distance, 2.0);
+
+    // Undirected edges are followed backwards
+    let path = g.shortest_path(2, 0, &weighted("weight")).unwrap().unwrap();
+    assert_eq!(path.vertices, vec![2, 1, 0]);
+    assert_eq!(path.edges, vec![2, 1]);
+
+    let path = g.shortest_path(1, 1, &weighted("weight")).unwrap().unwrap();
+    assert_eq!(path.vertices, vec![1]);
+    assert!(path.edges.is_empty());
+
+    assert_eq!(g.shortest_path(0, 3, &weighted("weight")).unwrap(), None);
+    assert!(g.shortest_path(0, 4, &weighted("weight")).is_err());
+}
+
+#[test]
+fn test_astar() {
+    // A grid with edges as long as the distance between their vertices
+    let side = 10;
+    let mut edges = Vec::new();
+    for vertex in 0..side * side {
+        if vertex % side + 1 < side {
+            edges.push((vertex, vertex + 1, 1.0));
+        }
+        if vertex + side < side * side {
+            edges.push((vertex, vertex + side, 1.0));
+        }
+    }
+    let mut g = weighted_graph(false, side * side, &edges);
+    g.new_vertex_property("pos", PropertyType::VectorDouble);
+    let positions = (0..side * side)
+        .map(|vertex| vec![(vertex % side) as f64, (vertex / side) as f64])
+        .collect();
+    g.set_vertex_property::<Vec<f64>>("pos", positions).unwrap();
+
+    let options = PathOptions {
+        position: Some("pos".to_string()),
+        ..weighted("weight")
+    };
+    let target = side * side - 1;
+    let path = g.shortest_path(0, target, &options).unwrap().unwrap();
+    assert_eq!(path.distance, 18.0);
+    assert_eq!(path.vertices.len(), 19);
+    let dijkstra = g
+        .shortest_path(0, target, &weighted("weight"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.distance, dijkstra.distance);
+
+    // The search stops early, far away vertices are not reached
+    let paths = g.astar(0, 2, Some("weight"), "pos").unwrap();
+    assert_eq!(paths.distances[2], 2.0);
+    assert_eq!(paths.distances[target as usize], f64::INFINITY);
+
+    assert!(g.astar(0, 2, Some("weight"), "weight").is_err());
+}
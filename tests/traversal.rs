@@ -0,0 +1,121 @@
+mod common;
+
+use common::{graph, lesmis};
+use gt_graph_wasm::traversal::{Visitor, UNREACHABLE};
+
+/// Records all events as strings
+#[derive(Default)]
+struct Events(Vec<String>);
+
+impl Visitor for Events {
+    fn discover_vertex(&mut self, vertex: u64) {
+        self.0.push(format!("discover {}", vertex));
+    }
+
+    fn finish_vertex(&mut self, vertex: u64) {
+        self.0.push(format!("finish {}", vertex));
+    }
+
+    fn tree_edge(&mut self, edge: u64, source: u64, target: u64) {
+        self.0.push(format!("tree {} {}->{}", edge, source, target));
+    }
+}
+
+#[test]
+fn test_bfs_directed() {
+    // 0 -> 1 -> 3, 0 -> 2 -> 3, 4 is not reachable
+    let g = graph(true, 5, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)]);
+    let search = g.bfs(0).unwrap();
+    assert_eq!(search.order, vec![0, 1, 2, 3]);
+    assert_eq!(search.predecessors, vec![0, 0, 0, 1, 4]);
+    assert_eq!(search.distances, vec![0, 1, 1, 2, UNREACHABLE]);
+
+    // Edges are followed in their direction only
+    let search = g.bfs(3).unwrap();
+    assert_eq!(search.order, vec![3, 0, 1, 2]);
+    assert_eq!(search.distances, vec![1, 2, 2, 0, UNREACHABLE]);
+
+    assert!(g.bfs(5).is_err());
+    assert!(g.dfs(5).is_err());
+}
+
+#[test]
+fn test_bfs_events() {
+    let g = graph(false, 3, &[(0, 1), (1, 1), (2, 1)]);
+    let mut events = Events::default();
+    g.bfs_with(1, &mut events).unwrap();
+    assert_eq!(
+        events.0,
+        vec![
+            "discover 1",
+            "tree 0 1->0",
+            "discover 0",
+            "tree 2 1->2",
+            "discover 2",
+            "finish 1",
+            "finish 0",
+            "finish 2",
+        ]
+    );
+}
+
+#[test]
+fn test_dfs_events() {
+    // 0 -> 1 -> 2, 0 -> 3, 2 -> 0 (back edge)
+    let g = graph(true, 4, &[(0, 1), (0, 3), (1, 2), (2, 0)]);
+    let mut events = Events::default();
+    let search = g.dfs_with(0, &mut events).unwrap();
+    assert_eq!(search.order, vec![0, 1, 2, 3]);
+    assert_eq!(search.predecessors, vec![0, 0, 1, 0]);
+    assert_eq!(search.distances, vec![0, 1, 2, 1]);
+    assert_eq!(
+        events.0,
+        vec![
+            "discover 0",
+            "tree 0 0->1",
+            "discover 1",
+            "tree 2 1->2",
+            "discover 2",
+            "finish 2",
+            "finish 1",
+            "tree 1 0->3",
+            "discover 3",
+            "finish 3",
+            "finish 0",
+        ]
+    );
+}
+
+#[test]
+fn test_dfs_long_path() {
+    // Deep enough to overflow a recursive search on a small stack
+    let n = 100_000;
+    let edges: Vec<(u64, u64)> = (0..n - 1).map(|v| (v, v + 1)).collect();
+    let g = graph(true, n, &edges);
+    let search = g.dfs(0).unwrap();
+    assert_eq!(search.order.len() as u64, n);
+    assert_eq!(search.distances[n as usize - 1], n - 1);
+}
+
+#[test]
+fn test_bfs_tree() {
+    let g = lesmis();
+    let search = g.bfs(11).unwrap();
+    assert_eq!(search.order.len(), 77);
+    for vertex in 0..77 {
+        let predecessor = search.predecessors[vertex as usize];
+        if vertex == 11 {
+            assert_eq!(predecessor, 11);
+            continue;
+        }
+        assert!(g.out_neighbors(predecessor).contains(vertex));
+        assert_eq!(
+            search.distances[vertex as usize],
+            search.distances[predecessor as usize] + 1
+        );
+        // Breadth-first distances are shortest: no neighbor is more than one step closer
+        for neighbor in g.out_neighbors(vertex) {
+            assert!(search.distances[neighbor as usize] + 1 >= search.distances[vertex as usize]);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use gt_graph_wasm::decode::decodebuffer;
+use gt_graph_wasm::{Graph, GraphFile};
+
+fn network() -> Vec<u8> {
+    let data = include_bytes!("../test_data/network.gt.zst");
+    decodebuffer(data).unwrap()
+}
+
+/// Graph with 3 vertices (0 -> 1, 0 -> 2) and the given graph properties
+fn file_with_properties(properties: &[(u8, &[u8])]) -> Vec<u8> {
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(0x01);
+    data.extend_from_slice(&3u64.to_le_bytes());
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(&[1, 2]);
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    data.extend_from_slice(&(properties.len() as u64).to_le_bytes());
+    for (i, (value_type, payload)) in properties.iter().enumerate() {
+        let name = format!("p{}", i);
+        data.push(0x00);
+        data.extend_from_slice(&(name.len() as u64).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.push(*value_type);
+        data.extend_from_slice(payload);
+    }
+    data
+}
+
+fn vector(len: u64, values: &[u8]) -> Vec<u8> {
+    let mut data = len.to_le_bytes().to_vec();
+    data.extend_from_slice(values);
+    data
+}
+
+#[test]
+fn test_roundtrip_network() {
+    let data = network();
+    let graph_file = GraphFile::try_from(data.as_slice()).unwrap();
+    assert_eq!(graph_file.to_bytes(), data);
+}
+
+#[test]
+fn test_roundtrip_zstd() {
+    let data = network();
+    let graph = Graph::try_from(data.clone()).unwrap();
+
+    let compressed = graph.to_bytes_zstd(3).unwrap();
+    assert!(compressed.len() < data.len());
+    assert_eq!(decodebuffer(&compressed).unwrap(), data);
+
+    let graph = Graph::try_from(compressed).unwrap();
+    assert_eq!(graph.num_vertices(), 77);
+    assert_eq!(graph.num_edges(), 254);
+}
+
+#[test]
+fn test_roundtrip_property_types() {
+    let string = vector(3, b"abc");
+    let mut vector_string = 2u64.to_le_bytes().to_vec();
+    vector_string.extend(vector(1, b"a"));
+    vector_string.extend(vector(0, b""));
+
+    // 1.0 as long double with garbage in the padding bytes
+    let mut long_double = vec![0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f];
+    long_double.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]);
+
+    let data = file_with_properties(&[
+        (0x00, &[1]),
+        (0x01, &(-2i16).to_le_bytes()),
+        (0x02, &(-3i32).to_le_bytes()),
+        (0x03, &(-4i64).to_le_bytes()),
+        (0x04, &(0.5f64).to_le_bytes()),
+        (0x05, &long_double),
+        (0x06, &string),
+        (0x07, &vector(3, &[1, 0, 1])),
+        (0x08, &vector(1, &7i16.to_le_bytes())),
+        (0x09, &vector(1, &8i32.to_le_bytes())),
+        (0x0a, &vector(1, &9i64.to_le_bytes())),
+        (0x0b, &vector(1, &1.5f64.to_le_bytes())),
+        (0x0c, &vector(1, &long_double)),
+        (0x0d, &vector_string),
+        (0x0e, &vector(4, b"\x80\x04N.")),
+    ]);
+
+    let graph_file = GraphFile::try_from(data.as_slice()).unwrap();
+    assert_eq!(graph_file.properties.len(), 15);
+    assert_eq!(graph_file.to_bytes(), data);
+}
+
+#[test]
+fn test_roundtrip_index_width() {
+    // 300 vertices need 2 bytes per neighbor index
+    let mut data = vec![0xe2, 0x9b, 0xbe, 0x20, 0x67, 0x74, 0x01, 0x00];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(0x00);
+    data.extend_from_slice(&300u64.to_le_bytes());
+    for v in 0..300u16 {
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&(299 - v).to_le_bytes());
+    }
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    let graph_file = GraphFile::try_from(data.as_slice()).unwrap();
+    assert_eq!(graph_file.num_edges, 300);
+    assert_eq!(graph_file.to_bytes(), data);
+}